@@ -1,12 +1,26 @@
 use std::collections::VecDeque;
 use std::mem;
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use futures::{Async, AsyncSink, Future, Poll, Sink, Stream};
 use futures::stream::FuturesUnordered;
+use futures::task;
 
 use super::async_server_error::AsyncServerError;
+use super::error_policy::ErrorAction;
 use super::finite_service::FiniteService;
+use super::push_channel::PushReceiver;
+use super::rate_limit_policy::RateLimitRejection;
+use super::rate_limiter::RateLimiter;
+use super::server_metrics::ServerMetrics;
+use super::server_options::ServerOptions;
+use super::session_id::SessionId;
+use super::session_observer::SessionObserver;
+use super::shutdown_mode::ShutdownMode;
 use super::status::Status;
+use super::termination_reason::TerminationReason;
+use super::timed_request::TimedRequest;
 
 pub struct ActiveServer<S, T>
 where
@@ -15,9 +29,26 @@ where
 {
     connection: T,
     service: S,
-    live_requests: FuturesUnordered<S::Future>,
+    live_requests: FuturesUnordered<TimedRequest<S::Future>>,
     live_responses: VecDeque<S::Response>,
+    push_receiver: Option<PushReceiver<S::Response>>,
+    pushed_message: Option<S::Response>,
     status: Status<AsyncServerError<S::Error, T::Error>>,
+    local_address: SocketAddr,
+    peer_address: SocketAddr,
+    observer: SessionObserver<S::Error, T::Error>,
+    options: ServerOptions,
+    rate_limiter: Option<RateLimiter>,
+    rate_limit_rejection: Option<RateLimitRejection<S::Request, S::Response>>,
+    stop_requested: bool,
+    unflushed_responses: bool,
+    retrying_output: bool,
+    peer_disconnected: bool,
+    requests_received: usize,
+    responses_sent: usize,
+    messages_pushed: usize,
+    consecutive_errors: usize,
+    prepared: bool,
 }
 
 impl<S, T, E> ActiveServer<S, T>
@@ -27,33 +58,290 @@ where
         + Stream<Item = S::Request, Error = E>,
 {
     pub fn new(connection: T, service: S) -> Self {
+        let address = SocketAddr::from(([0, 0, 0, 0], 0));
+        let observer = SessionObserver::new(SessionId::next(), None);
+
+        Self::with_options(
+            connection,
+            service,
+            address,
+            address,
+            observer,
+            ServerOptions::default(),
+        )
+    }
+
+    pub(crate) fn with_options(
+        connection: T,
+        service: S,
+        local_address: SocketAddr,
+        peer_address: SocketAddr,
+        observer: SessionObserver<S::Error, T::Error>,
+        options: ServerOptions,
+    ) -> Self {
         Self {
             connection,
             service,
+            local_address,
+            peer_address,
+            observer,
+            options,
+            rate_limiter: None,
+            rate_limit_rejection: None,
             live_requests: FuturesUnordered::new(),
             live_responses: VecDeque::new(),
+            push_receiver: None,
+            pushed_message: None,
             status: Status::Active,
+            stop_requested: false,
+            unflushed_responses: false,
+            retrying_output: false,
+            peer_disconnected: false,
+            requests_received: 0,
+            responses_sent: 0,
+            messages_pushed: 0,
+            consecutive_errors: 0,
+            prepared: false,
         }
     }
 
+    pub fn with_rate_limiter(
+        mut self,
+        rate_limiter: RateLimiter,
+        rejection: Option<RateLimitRejection<S::Request, S::Response>>,
+    ) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self.rate_limit_rejection = rejection;
+        self
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer_address
+    }
+
+    pub fn is_prepared(&self) -> bool {
+        self.prepared
+    }
+
+    pub fn in_flight_requests(&self) -> usize {
+        self.live_requests.len()
+    }
+
+    pub fn requests_received(&self) -> usize {
+        self.requests_received
+    }
+
+    pub fn responses_sent(&self) -> usize {
+        self.responses_sent
+    }
+
+    pub fn messages_pushed(&self) -> usize {
+        self.messages_pushed
+    }
+
+    pub fn session_id(&self) -> SessionId {
+        self.observer.session_id()
+    }
+
+    pub fn observer(&self) -> &SessionObserver<S::Error, T::Error> {
+        &self.observer
+    }
+
+    pub fn metrics(&self) -> &Arc<ServerMetrics> {
+        &self.options.metrics
+    }
+
     pub fn shutdown(
         &mut self,
     ) -> Poll<(), AsyncServerError<S::Error, T::Error>> {
-        match self.service.force_stop() {
-            Ok(()) => Ok(Async::Ready(())),
-            Err(error) => Err(AsyncServerError::ServiceShutdownError(error)),
+        if !self.stop_requested {
+            self.stop_requested = true;
+
+            self.service
+                .request_stop()
+                .map_err(AsyncServerError::ServiceShutdownError)?;
+        }
+
+        if self.options.shutdown_mode == ShutdownMode::Graceful {
+            try_ready!(self.drain());
+        }
+
+        self.service
+            .poll_stop()
+            .map_err(AsyncServerError::ServiceShutdownError)
+    }
+
+    fn prepare(&mut self) -> Poll<(), AsyncServerError<S::Error, T::Error>> {
+        if !self.prepared {
+            try_ready!(
+                self.service
+                    .poll_prepared()
+                    .map_err(AsyncServerError::ServicePreparationError)
+            );
+
+            debug!("session {}: service prepared", self.session_id());
+
+            self.prepared = true;
+            self.push_receiver = self.service.push_receiver();
+        }
+
+        Ok(Async::Ready(()))
+    }
+
+    fn drain(&mut self) -> Poll<(), AsyncServerError<S::Error, T::Error>> {
+        while self.status.is_active() {
+            self.try_to_get_new_response()
+                .try_to_send_pushed_message()
+                .try_to_send_responses()
+                .try_to_flush_responses()
+                .check_if_drained();
+        }
+
+        self.poll_status()
+    }
+
+    fn can_accept_new_request(&self) -> bool {
+        if self.peer_disconnected {
+            return false;
+        }
+
+        match self.options.max_pending_requests {
+            Some(limit) => self.live_requests.len() < limit,
+            None => true,
+        }
+    }
+
+    fn rate_limit_allows_reading(&mut self) -> bool {
+        if self.rate_limit_rejection.is_some() {
+            return true;
+        }
+
+        match self.poll_rate_limiter() {
+            Ok(Async::Ready(())) => true,
+            Ok(Async::NotReady) => {
+                self.status.update(Status::WouldBlock);
+                false
+            }
+            Err(error) => {
+                self.status.update(Status::Error(error));
+                false
+            }
+        }
+    }
+
+    fn take_rate_limit_token(&mut self) -> bool {
+        match self.poll_rate_limiter() {
+            Ok(Async::Ready(())) => {
+                if let Some(ref mut rate_limiter) = self.rate_limiter {
+                    rate_limiter.acquire();
+                }
+
+                true
+            }
+            Ok(Async::NotReady) => false,
+            Err(error) => {
+                self.status.update(Status::Error(error));
+                false
+            }
+        }
+    }
+
+    fn poll_rate_limiter(
+        &mut self,
+    ) -> Poll<(), AsyncServerError<S::Error, T::Error>> {
+        match self.rate_limiter {
+            Some(ref mut rate_limiter) => rate_limiter
+                .poll_ready()
+                .map_err(AsyncServerError::RateLimitTimerError),
+            None => Ok(Async::Ready(())),
+        }
+    }
+
+    fn reject_request(&mut self, request: S::Request) {
+        self.options.metrics.request_rate_limited();
+
+        if let Some(ref rejection) = self.rate_limit_rejection {
+            debug!("session {}: request rate limited", self.session_id());
+
+            self.options.metrics.response_queued();
+            self.live_responses.push_back(rejection(&request));
+        }
+    }
+
+    fn service_is_ready(&mut self) -> bool {
+        // A finished service gets no new requests. The session then ends in
+        // check_if_finished once the pending work is done.
+        match self.service.has_finished() {
+            Ok(false) => {}
+            Ok(true) => return false,
+            Err(error) => {
+                let action = self.options.error_policy.finished_check;
+
+                self.update_status(
+                    Status::Error(
+                        AsyncServerError::ServiceFinishedCheckError(error),
+                    ),
+                    action,
+                );
+
+                return false;
+            }
+        }
+
+        match self.service.poll_ready() {
+            Ok(Async::Ready(())) => true,
+            Ok(Async::NotReady) => {
+                self.status.update(Status::WouldBlock);
+                false
+            }
+            Err(error) => {
+                self.status.update(Status::Error(
+                    AsyncServerError::ServiceReadinessError(error),
+                ));
+                false
+            }
         }
     }
 
     fn try_to_get_new_request(&mut self) -> &mut Self {
-        if self.status.is_running() {
+        let can_receive = self.status.is_running()
+            && !self.retrying_output
+            && self.can_accept_new_request()
+            && self.rate_limit_allows_reading()
+            && self.service_is_ready();
+
+        if can_receive {
             let new_request = self.connection.poll();
 
             if let Ok(Async::Ready(Some(request))) = new_request {
-                self.live_requests.push(self.service.call(request));
+                self.consecutive_errors = 0;
+                self.requests_received += 1;
+                self.options.metrics.request_received();
+                self.observer.request();
+
+                if self.take_rate_limit_token() {
+                    let response = self.service.call(request);
+                    let metrics = self.options.metrics.clone();
+
+                    self.live_requests
+                        .push(TimedRequest::new(response, metrics));
+                } else {
+                    self.reject_request(request);
+                }
             } else {
-                self.status.update(
+                if let Ok(Async::Ready(None)) = new_request {
+                    self.peer_disconnected = true;
+                }
+
+                let action = self.options.error_policy.request_decode;
+
+                self.update_status(
                     new_request.map_err(AsyncServerError::NewRequestError),
+                    action,
                 );
             }
         }
@@ -66,10 +354,14 @@ where
             let maybe_response = self.live_requests.poll();
 
             if let Ok(Async::Ready(Some(response))) = maybe_response {
+                self.options.metrics.response_queued();
                 self.live_responses.push_back(response);
             } else {
-                self.status.update(
+                let action = self.options.error_policy.response;
+
+                self.update_status(
                     maybe_response.map_err(AsyncServerError::NewResponseError),
+                    action,
                 );
             }
         }
@@ -77,19 +369,90 @@ where
         self
     }
 
+    fn try_to_get_pushed_message(&mut self) -> &mut Self {
+        if self.stop_requested {
+            self.push_receiver = None;
+            return self;
+        }
+
+        let can_push = self.status.is_running()
+            && !self.retrying_output
+            && self.pushed_message.is_none()
+            && self.live_responses.is_empty();
+
+        if can_push {
+            let poll_result = match self.push_receiver {
+                Some(ref mut receiver) => receiver.poll(),
+                None => return self,
+            };
+
+            match poll_result {
+                Ok(Async::Ready(Some(message))) => {
+                    self.pushed_message = Some(message);
+                }
+                Ok(Async::NotReady) => {}
+                _ => self.push_receiver = None,
+            }
+        }
+
+        self
+    }
+
+    fn try_to_send_pushed_message(&mut self) -> &mut Self {
+        if !self.status.is_running() {
+            return self;
+        }
+
+        if let Some(message) = self.pushed_message.take() {
+            match self.connection.start_send(message) {
+                Ok(AsyncSink::Ready) => {
+                    self.consecutive_errors = 0;
+                    self.messages_pushed += 1;
+                    self.options.metrics.message_pushed();
+                    self.unflushed_responses = true;
+                }
+                Ok(AsyncSink::NotReady(message)) => {
+                    self.pushed_message = Some(message);
+                    self.status.update(Status::WouldBlock);
+                }
+                error => {
+                    let action = self.options.error_policy.send;
+
+                    self.update_output_status(
+                        error.map_err(AsyncServerError::SendResponseError),
+                        action,
+                    )
+                }
+            }
+        }
+
+        self
+    }
+
     fn try_to_send_responses(&mut self) -> &mut Self {
         if self.status.is_running() {
             while let Some(response) = self.live_responses.pop_front() {
                 match self.connection.start_send(response) {
-                    Ok(AsyncSink::Ready) => (),
+                    Ok(AsyncSink::Ready) => {
+                        self.consecutive_errors = 0;
+                        self.responses_sent += 1;
+                        self.options.metrics.response_sent();
+                        self.observer.response();
+                        self.unflushed_responses = true;
+                    }
                     Ok(AsyncSink::NotReady(response)) => {
                         self.live_responses.push_front(response);
                         self.status.update(Status::WouldBlock);
+                        break;
                     }
                     error => {
-                        self.status.update(
+                        let action = self.options.error_policy.send;
+
+                        self.update_output_status(
                             error.map_err(AsyncServerError::SendResponseError),
-                        )
+                            action,
+                        );
+                        break;
                     }
                 };
             }
@@ -100,10 +463,25 @@ where
 
     fn try_to_flush_responses(&mut self) -> &mut Self {
         if self.status.is_running() {
-            self.status.update(
-                self.connection
-                    .poll_complete()
-                    .map_err(AsyncServerError::FlushResponsesError),
+            let flush_result = self.connection.poll_complete();
+
+            if let Ok(Async::Ready(())) = flush_result {
+                if self.unflushed_responses {
+                    self.unflushed_responses = false;
+                    self.options.metrics.responses_flushed();
+                }
+
+                if self.retrying_output {
+                    self.retrying_output = false;
+                    self.consecutive_errors = 0;
+                }
+            }
+
+            let action = self.options.error_policy.flush;
+
+            self.update_output_status(
+                flush_result.map_err(AsyncServerError::FlushResponsesError),
+                action,
             );
         }
 
@@ -113,12 +491,27 @@ where
     fn check_if_finished(&mut self) {
         if self.status.is_running() {
             let no_pending_requests = self.live_requests.is_empty();
-            let no_pending_responses = self.live_responses.is_empty();
+            let no_pending_responses = self.live_responses.is_empty()
+                && self.pushed_message.is_none()
+                && !self.retrying_output;
 
             if no_pending_requests && no_pending_responses {
-                let service_status = match self.service.has_finished() {
-                    Ok(true) => Status::Finished,
-                    Ok(false) => Status::Active,
+                if self.peer_disconnected {
+                    self.status.update(Status::Finished);
+                    return;
+                }
+
+                let service_status = match self.service.poll_finished() {
+                    Ok(Async::Ready(())) if !self.unflushed_responses => {
+                        // Finished ranks below WouldBlock, so a transport
+                        // waiting for more requests would otherwise hide the
+                        // finish. Nothing is left to receive, send or flush,
+                        // so no pending work is lost by ending here.
+                        self.status = Status::Finished;
+                        return;
+                    }
+                    Ok(Async::Ready(())) => Status::Finished,
+                    Ok(Async::NotReady) => Status::Active,
                     Err(error) => {
                         Status::Error(
                             AsyncServerError::ServiceFinishedCheckError(error),
@@ -126,7 +519,80 @@ where
                     }
                 };
 
-                self.status.update(service_status);
+                let action = self.options.error_policy.finished_check;
+
+                self.update_status(service_status, action);
+            }
+        }
+    }
+
+    fn update_status<R>(&mut self, status_update: R, action: ErrorAction)
+    where
+        R: Into<Status<AsyncServerError<S::Error, T::Error>>>,
+    {
+        match status_update.into() {
+            Status::Error(error) => self.handle_error(error, action),
+            status_update => self.status.update(status_update),
+        }
+    }
+
+    fn update_output_status<R>(
+        &mut self,
+        status_update: R,
+        action: ErrorAction,
+    ) where
+        R: Into<Status<AsyncServerError<S::Error, T::Error>>>,
+    {
+        let status_update = status_update.into();
+
+        if let Status::Error(_) = status_update {
+            if action == ErrorAction::Retry {
+                self.retrying_output = true;
+            }
+        }
+
+        self.update_status(status_update, action);
+    }
+
+    fn handle_error(
+        &mut self,
+        error: AsyncServerError<S::Error, T::Error>,
+        action: ErrorAction,
+    ) {
+        let error_limit = self.options.error_policy.consecutive_error_limit();
+
+        self.consecutive_errors += 1;
+
+        let too_many_errors = self.consecutive_errors > error_limit;
+
+        if action == ErrorAction::Fatal || too_many_errors {
+            self.status.update(Status::Error(error));
+            return;
+        }
+
+        warn!(
+            "session {}: {} ({:?})",
+            self.session_id(),
+            error.name(),
+            action
+        );
+
+        self.options.metrics.error_occurred(error.name());
+        self.observer.error(&error);
+
+        task::current().notify();
+        self.status.update(Status::WouldBlock);
+    }
+
+    fn check_if_drained(&mut self) {
+        if self.status.is_active() {
+            let no_pending_requests = self.live_requests.is_empty();
+            let no_pending_responses = self.live_responses.is_empty()
+                && self.pushed_message.is_none()
+                && !self.retrying_output;
+
+            if no_pending_requests && no_pending_responses {
+                self.status.update(Status::Finished);
             }
         }
     }
@@ -146,18 +612,404 @@ where
     T: Sink<SinkItem = S::Response, SinkError = E>
         + Stream<Item = S::Request, Error = E>,
 {
-    type Item = ();
+    type Item = TerminationReason;
     type Error = AsyncServerError<S::Error, T::Error>;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        try_ready!(self.prepare());
+
         while self.status.is_active() {
             self.try_to_get_new_request()
                 .try_to_get_new_response()
+                .try_to_get_pushed_message()
+                .try_to_send_pushed_message()
                 .try_to_send_responses()
                 .try_to_flush_responses()
                 .check_if_finished();
         }
 
-        self.poll_status()
+        try_ready!(self.poll_status());
+
+        if self.peer_disconnected {
+            Ok(Async::Ready(TerminationReason::PeerDisconnected))
+        } else {
+            Ok(Async::Ready(TerminationReason::ServiceFinished))
+        }
+    }
+}
+
+impl<S, T> Drop for ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Stream<Item = S::Request>,
+{
+    fn drop(&mut self) {
+        self.options.metrics.session_dropped(
+            self.live_requests.len(),
+            self.live_responses.len(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use futures::Future;
+    use tokio_core::reactor::Core;
+
+    use super::super::async_server_error::AsyncServerError;
+    use super::super::error_policy::{ErrorAction, ErrorPolicy};
+    use super::super::middleware::ConcurrencyLimit;
+    use super::super::push_channel::push_channel;
+    use super::super::rate_limiter::RateLimiter;
+    use super::super::termination_reason::TerminationReason;
+    use super::super::testing::{
+        active_server, active_server_with_options, Call, CallLog,
+        MockService, ScriptedTransport, ServerOptions,
+    };
+
+    #[test]
+    fn request_and_response_round_trip() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1);
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::PeerDisconnected);
+        call_log.assert_calls(&[
+            Call::Received(1),
+            Call::Called(1),
+            Call::Sent(10),
+            Call::Flushed,
+        ]);
+    }
+
+    #[test]
+    fn service_finishes_after_requests() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .respond(20)
+            .finish_after(2)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .request(3)
+            .keep_open();
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        assert_eq!(call_log.responses(), vec![10, 20]);
+        assert!(!call_log.calls().contains(&Call::Called(3)));
+    }
+
+    #[test]
+    fn blocked_transport_is_flushed_before_sending_more() {
+        let options = ServerOptions::default();
+        let metrics = options.metrics.clone();
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .respond(20)
+            .finish_after(2)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .send_capacity(1)
+            .keep_open();
+
+        let reason = active_server_with_options(service, transport, options)
+            .wait()
+            .unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        call_log.assert_order(&[
+            Call::Sent(10),
+            Call::Flushed,
+            Call::Sent(20),
+            Call::Flushed,
+        ]);
+        assert_eq!(metrics.snapshot().responses_sent, 2);
+    }
+
+    #[test]
+    fn delayed_response_is_sent_after_later_ones() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond_after(10, 3)
+            .respond(20)
+            .finish_on(2)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .pause()
+            .request(2)
+            .keep_open();
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        call_log.assert_order(&[
+            Call::Received(1),
+            Call::Called(1),
+            Call::Received(2),
+            Call::Called(2),
+            Call::Sent(20),
+            Call::Sent(10),
+        ]);
+    }
+
+    #[test]
+    fn service_error_ends_the_session() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, &'static str>::new()
+            .fail("failure")
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1);
+
+        let result = active_server(service, transport).wait();
+
+        assert!(result.is_err());
+        assert!(call_log.responses().is_empty());
+    }
+
+    fn flush_failing_server(
+        call_log: &CallLog<u32, u32>,
+        action: ErrorAction,
+        flush_errors: usize,
+    ) -> Result<TerminationReason, ()> {
+        let mut options = ServerOptions::default();
+
+        options.error_policy =
+            ErrorPolicy::new().flush(action).max_consecutive_errors(1);
+
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .respond(20)
+            .with_call_log(call_log.clone());
+        let mut transport =
+            ScriptedTransport::<_, _, ()>::new(call_log.clone())
+                .request(1)
+                .request(2);
+
+        for _ in 0..flush_errors {
+            transport = transport.fail_flush(());
+        }
+
+        active_server_with_options(service, transport, options)
+            .wait()
+            .map_err(|_| ())
+    }
+
+    #[test]
+    fn fatal_flush_error_ends_the_session() {
+        let call_log = CallLog::new();
+
+        let result = flush_failing_server(&call_log, ErrorAction::Fatal, 1);
+
+        assert!(result.is_err());
+        assert!(!call_log.calls().contains(&Call::Received(2)));
+    }
+
+    #[test]
+    fn ignored_flush_error_keeps_reading_requests() {
+        let call_log = CallLog::new();
+
+        let result = flush_failing_server(&call_log, ErrorAction::Ignore, 1);
+
+        assert_eq!(result, Ok(TerminationReason::PeerDisconnected));
+        call_log.assert_order(&[
+            Call::Sent(10),
+            Call::Received(2),
+            Call::Sent(20),
+            Call::Flushed,
+        ]);
+    }
+
+    #[test]
+    fn retried_flush_completes_before_reading_requests() {
+        let call_log = CallLog::new();
+
+        let result = flush_failing_server(&call_log, ErrorAction::Retry, 1);
+
+        assert_eq!(result, Ok(TerminationReason::PeerDisconnected));
+        call_log.assert_order(&[
+            Call::Sent(10),
+            Call::Flushed,
+            Call::Received(2),
+            Call::Sent(20),
+            Call::Flushed,
+        ]);
+    }
+
+    #[test]
+    fn retries_are_bounded_by_consecutive_error_limit() {
+        let call_log = CallLog::new();
+
+        let result = flush_failing_server(&call_log, ErrorAction::Retry, 2);
+
+        assert!(result.is_err());
+        assert!(!call_log.calls().contains(&Call::Flushed));
+        assert!(!call_log.calls().contains(&Call::Received(2)));
+    }
+
+    #[test]
+    fn ignored_send_error_drops_the_response() {
+        let mut options = ServerOptions::default();
+
+        options.error_policy = ErrorPolicy::new().send(ErrorAction::Ignore);
+
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .respond(20)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .fail_send(());
+
+        let reason = active_server_with_options(service, transport, options)
+            .wait()
+            .unwrap();
+
+        assert_eq!(reason, TerminationReason::PeerDisconnected);
+        assert_eq!(call_log.responses(), vec![20]);
+    }
+
+    #[test]
+    fn rate_limited_requests_wait_for_a_token() {
+        let mut core = Core::new().unwrap();
+        let interval = Duration::from_millis(20);
+        let rate_limiter = RateLimiter::new(1, interval, &core.handle());
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .respond(20)
+            .respond(30)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .request(3);
+        let server = active_server(service, transport)
+            .with_rate_limiter(rate_limiter, None);
+        let started_at = Instant::now();
+
+        let reason = core.run(server).unwrap();
+
+        assert_eq!(reason, TerminationReason::PeerDisconnected);
+        assert_eq!(call_log.responses(), vec![10, 20, 30]);
+        assert!(started_at.elapsed() >= interval * 2);
+    }
+
+    #[test]
+    fn rate_limited_requests_are_rejected_when_configured() {
+        let options = ServerOptions::default();
+        let metrics = options.metrics.clone();
+        let core = Core::new().unwrap();
+        let interval = Duration::from_secs(60);
+        let rate_limiter = RateLimiter::new(1, interval, &core.handle());
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .request(3);
+        let rejection = Arc::new(|request: &u32| request + 100);
+        let server = active_server_with_options(service, transport, options)
+            .with_rate_limiter(rate_limiter, Some(rejection));
+
+        let reason = server.wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::PeerDisconnected);
+        assert_eq!(call_log.responses(), vec![10, 102, 103]);
+        assert!(!call_log.calls().contains(&Call::Called(2)));
+
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(snapshot.requests_received, 3);
+        assert_eq!(snapshot.rate_limited_requests, 2);
+    }
+
+    #[test]
+    fn pushed_messages_are_sent_and_counted_apart() {
+        let options = ServerOptions::default();
+        let metrics = options.metrics.clone();
+        let (mut sender, receiver) = push_channel(2);
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .with_push_receiver(receiver)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .pause()
+            .pause();
+
+        sender.try_push(100).unwrap();
+        sender.try_push(200).unwrap();
+
+        let server = active_server_with_options(service, transport, options);
+        let reason = server.wait().unwrap();
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(reason, TerminationReason::PeerDisconnected);
+        assert_eq!(call_log.responses(), vec![10, 100, 200]);
+        assert_eq!(snapshot.responses_sent, 1);
+        assert_eq!(snapshot.pushed_messages, 2);
+    }
+
+    #[test]
+    fn requests_wait_while_the_service_is_not_ready() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond_after(10, 2)
+            .respond(20)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2);
+        let service = ConcurrencyLimit::new(service, 1);
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::PeerDisconnected);
+        call_log.assert_order(&[
+            Call::Called(1),
+            Call::Sent(10),
+            Call::Received(2),
+            Call::Sent(20),
+        ]);
+    }
+
+    #[test]
+    fn readiness_error_ends_the_session_before_reading() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .fail_readiness(())
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1);
+
+        match active_server(service, transport).wait() {
+            Err(AsyncServerError::ServiceReadinessError(())) => {}
+            result => panic!("unexpected session result: {:?}", result),
+        }
+
+        assert!(call_log.calls().is_empty());
     }
 }
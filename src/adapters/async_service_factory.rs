@@ -0,0 +1,42 @@
+use std::io;
+
+use futures::IntoFuture;
+use tokio_service::{NewService, Service};
+
+use super::super::finite_service::FiniteService;
+use super::prepared_service::PreparedService;
+use super::prepared_service_error::PreparedServiceError;
+
+#[derive(Clone)]
+pub struct AsyncServiceFactory<F> {
+    factory: F,
+}
+
+impl<F> AsyncServiceFactory<F> {
+    pub fn new(factory: F) -> Self {
+        AsyncServiceFactory { factory }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.factory
+    }
+}
+
+impl<F, T> NewService for AsyncServiceFactory<F>
+where
+    F: Fn() -> T,
+    T: IntoFuture,
+    T::Item: FiniteService,
+{
+    type Request = <T::Item as Service>::Request;
+    type Response = <T::Item as Service>::Response;
+    type Error =
+        PreparedServiceError<T::Error, <T::Item as Service>::Error>;
+    type Instance = PreparedService<T::Future>;
+
+    fn new_service(&self) -> io::Result<Self::Instance> {
+        let service_future = (self.factory)().into_future();
+
+        Ok(PreparedService::new(service_future))
+    }
+}
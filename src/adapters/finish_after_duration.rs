@@ -0,0 +1,80 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use futures::{Async, Future, Poll};
+use tokio_core::reactor::{Handle, Timeout};
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+
+pub struct FinishAfterDuration<S> {
+    service: S,
+    deadline: Instant,
+    timeout: Timeout,
+    stopped: bool,
+}
+
+impl<S> FinishAfterDuration<S> {
+    pub fn new(
+        service: S,
+        duration: Duration,
+        handle: &Handle,
+    ) -> io::Result<Self> {
+        Ok(FinishAfterDuration {
+            service,
+            deadline: Instant::now() + duration,
+            timeout: Timeout::new(duration, handle)?,
+            stopped: false,
+        })
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for FinishAfterDuration<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.service.call(request)
+    }
+}
+
+impl<S> FiniteService for FinishAfterDuration<S>
+where
+    S: Service,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.stopped || Instant::now() >= self.deadline)
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.stopped = true;
+        Ok(())
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        match self.timeout.poll() {
+            Ok(Async::Ready(())) => return Ok(Async::Ready(())),
+            Ok(Async::NotReady) => {}
+            Err(error) => {
+                warn!("finish timer failed, finishing service: {}", error);
+
+                self.stopped = true;
+            }
+        }
+
+        if self.has_finished()? {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
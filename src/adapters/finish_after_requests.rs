@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+
+pub struct FinishAfterRequests<S> {
+    service: S,
+    limit: usize,
+    requests: AtomicUsize,
+    stopped: bool,
+}
+
+impl<S> FinishAfterRequests<S> {
+    pub fn new(service: S, limit: usize) -> Self {
+        FinishAfterRequests {
+            service,
+            limit,
+            requests: AtomicUsize::new(0),
+            stopped: false,
+        }
+    }
+
+    pub fn remaining_requests(&self) -> usize {
+        self.limit.saturating_sub(self.requests.load(Ordering::SeqCst))
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for FinishAfterRequests<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        self.service.call(request)
+    }
+}
+
+impl<S> FiniteService for FinishAfterRequests<S>
+where
+    S: Service,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.stopped || self.remaining_requests() == 0)
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.stopped = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+
+    use super::super::super::finite_service::FiniteService;
+    use super::super::super::termination_reason::TerminationReason;
+    use super::super::super::testing::{
+        active_server, Call, CallLog, MockService, ScriptedTransport,
+    };
+    use super::FinishAfterRequests;
+
+    #[test]
+    fn pipelined_requests_past_the_limit_are_not_called() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond_after(10, 2)
+            .respond_after(20, 2)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .request(3)
+            .keep_open();
+        let service = FinishAfterRequests::new(service, 2);
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        assert_eq!(call_log.responses(), vec![10, 20]);
+        assert!(!call_log.calls().contains(&Call::Called(3)));
+    }
+
+    #[test]
+    fn forced_stop_ends_an_idle_session() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .keep_open();
+        let mut service = FinishAfterRequests::new(service, 2);
+
+        service.force_stop().unwrap();
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        assert!(call_log.calls().is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+
+pub struct FinishOnRequest<S, F> {
+    service: S,
+    predicate: F,
+    finished: AtomicBool,
+}
+
+impl<S, F> FinishOnRequest<S, F>
+where
+    S: Service,
+    F: Fn(&S::Request) -> bool,
+{
+    pub fn new(service: S, predicate: F) -> Self {
+        FinishOnRequest {
+            service,
+            predicate,
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S, F> Service for FinishOnRequest<S, F>
+where
+    S: Service,
+    F: Fn(&S::Request) -> bool,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        if (self.predicate)(&request) {
+            self.finished.store(true, Ordering::SeqCst);
+        }
+
+        self.service.call(request)
+    }
+}
+
+impl<S, F> FiniteService for FinishOnRequest<S, F>
+where
+    S: Service,
+    F: Fn(&S::Request) -> bool,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.finished.load(Ordering::SeqCst))
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.finished.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+
+    use super::super::super::termination_reason::TerminationReason;
+    use super::super::super::testing::{
+        active_server, Call, CallLog, MockService, ScriptedTransport,
+    };
+    use super::FinishOnRequest;
+
+    #[test]
+    fn finishing_request_is_answered_before_the_session_ends() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond_after(10, 2)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .keep_open();
+        let service = FinishOnRequest::new(service, |request| *request == 1);
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        assert_eq!(call_log.responses(), vec![10]);
+        assert!(!call_log.calls().contains(&Call::Called(2)));
+    }
+}
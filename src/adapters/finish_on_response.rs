@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use futures::{Async, Future, Poll};
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+
+pub struct FinishOnResponse<S, F> {
+    service: S,
+    predicate: Arc<F>,
+    finished: Arc<AtomicBool>,
+}
+
+impl<S, F> FinishOnResponse<S, F>
+where
+    S: Service,
+    F: Fn(&S::Response) -> bool,
+{
+    pub fn new(service: S, predicate: F) -> Self {
+        FinishOnResponse {
+            service,
+            predicate: Arc::new(predicate),
+            finished: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S, F> Service for FinishOnResponse<S, F>
+where
+    S: Service,
+    F: Fn(&S::Response) -> bool,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = FinishOnResponseFuture<S::Future, F>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        FinishOnResponseFuture {
+            future: self.service.call(request),
+            predicate: self.predicate.clone(),
+            finished: self.finished.clone(),
+        }
+    }
+}
+
+impl<S, F> FiniteService for FinishOnResponse<S, F>
+where
+    S: Service,
+    F: Fn(&S::Response) -> bool,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.finished.load(Ordering::SeqCst))
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.finished.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+pub struct FinishOnResponseFuture<T, F> {
+    future: T,
+    predicate: Arc<F>,
+    finished: Arc<AtomicBool>,
+}
+
+impl<T, F> Future for FinishOnResponseFuture<T, F>
+where
+    T: Future,
+    F: Fn(&T::Item) -> bool,
+{
+    type Item = T::Item;
+    type Error = T::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let response = try_ready!(self.future.poll());
+
+        if (self.predicate)(&response) {
+            self.finished.store(true, Ordering::SeqCst);
+        }
+
+        Ok(Async::Ready(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+
+    use super::super::super::termination_reason::TerminationReason;
+    use super::super::super::testing::{
+        active_server, Call, CallLog, MockService, ScriptedTransport,
+    };
+    use super::FinishOnResponse;
+
+    #[test]
+    fn requests_after_the_finishing_response_are_not_called() {
+        let call_log = CallLog::new();
+        let service = MockService::<u32, u32, ()>::new()
+            .respond(10)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .request(2)
+            .keep_open();
+        let service = FinishOnResponse::new(service, |response| {
+            *response == 10
+        });
+
+        let reason = active_server(service, transport).wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        assert_eq!(call_log.responses(), vec![10]);
+        assert!(!call_log.calls().contains(&Call::Called(2)));
+    }
+}
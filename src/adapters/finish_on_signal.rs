@@ -0,0 +1,86 @@
+use futures::{Async, Future, Poll};
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+
+pub struct FinishOnSignal<S, F> {
+    service: S,
+    signal: Option<F>,
+    finished: bool,
+}
+
+impl<S, F> FinishOnSignal<S, F>
+where
+    S: Service,
+    F: Future,
+{
+    pub fn new(service: S, signal: F) -> Self {
+        FinishOnSignal {
+            service,
+            signal: Some(signal),
+            finished: false,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S, F> Service for FinishOnSignal<S, F>
+where
+    S: Service,
+    F: Future,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.service.call(request)
+    }
+}
+
+impl<S, F> FiniteService for FinishOnSignal<S, F>
+where
+    S: Service,
+    F: Future,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.finished)
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.finished = true;
+        self.signal = None;
+        Ok(())
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        let poll_result = match self.signal {
+            Some(ref mut signal) => signal.poll(),
+            None => Ok(Async::NotReady),
+        };
+
+        match poll_result {
+            Ok(Async::Ready(_)) => {
+                self.finished = true;
+                self.signal = None;
+            }
+            Ok(Async::NotReady) => {}
+            Err(_) => {
+                warn!("finish signal failed, finishing service");
+
+                self.finished = true;
+                self.signal = None;
+            }
+        }
+
+        if self.finished {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
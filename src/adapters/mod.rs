@@ -0,0 +1,25 @@
+mod async_service_factory;
+mod finish_after_duration;
+mod finish_after_requests;
+mod finish_on_request;
+mod finish_on_response;
+mod finish_on_signal;
+mod never_finishes;
+mod prepared_service;
+mod prepared_service_error;
+mod shared_service;
+mod shared_service_factory;
+
+pub use self::async_service_factory::AsyncServiceFactory;
+pub use self::finish_after_duration::FinishAfterDuration;
+pub use self::finish_after_requests::FinishAfterRequests;
+pub use self::finish_on_request::FinishOnRequest;
+pub use self::finish_on_response::{FinishOnResponse, FinishOnResponseFuture};
+pub use self::finish_on_signal::FinishOnSignal;
+pub use self::never_finishes::NeverFinishes;
+pub use self::prepared_service::{PreparedService, PreparedServiceFuture};
+pub use self::prepared_service_error::PreparedServiceError;
+pub use self::shared_service::SharedService;
+pub use self::shared_service_factory::SharedServiceFactory;
+
+pub(crate) use self::shared_service_factory::SharedInstanceStop;
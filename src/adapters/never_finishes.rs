@@ -0,0 +1,49 @@
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+
+pub struct NeverFinishes<S> {
+    service: S,
+    stopped: bool,
+}
+
+impl<S> NeverFinishes<S> {
+    pub fn new(service: S) -> Self {
+        NeverFinishes {
+            service,
+            stopped: false,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for NeverFinishes<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.service.call(request)
+    }
+}
+
+impl<S> FiniteService for NeverFinishes<S>
+where
+    S: Service,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.stopped)
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.stopped = true;
+        Ok(())
+    }
+}
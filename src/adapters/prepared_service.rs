@@ -0,0 +1,307 @@
+use std::marker::PhantomData;
+
+use futures::{Async, Future, Poll};
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+use super::prepared_service_error::PreparedServiceError;
+
+enum PreparedServiceState<F>
+where
+    F: Future,
+{
+    Preparing(F),
+    Ready(F::Item),
+    Stopped,
+}
+
+pub struct PreparedService<F>
+where
+    F: Future,
+    F::Item: FiniteService,
+{
+    state: PreparedServiceState<F>,
+    context: Option<ConnectionContext>,
+}
+
+impl<F> PreparedService<F>
+where
+    F: Future,
+    F::Item: FiniteService,
+{
+    pub fn new(service_future: F) -> Self {
+        PreparedService {
+            state: PreparedServiceState::Preparing(service_future),
+            context: None,
+        }
+    }
+
+    pub fn is_prepared(&self) -> bool {
+        match self.state {
+            PreparedServiceState::Ready(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn into_inner(self) -> Option<F::Item> {
+        match self.state {
+            PreparedServiceState::Ready(service) => Some(service),
+            _ => None,
+        }
+    }
+}
+
+impl<F> Service for PreparedService<F>
+where
+    F: Future,
+    F::Item: FiniteService,
+{
+    type Request = <F::Item as Service>::Request;
+    type Response = <F::Item as Service>::Response;
+    type Error =
+        PreparedServiceError<F::Error, <F::Item as Service>::Error>;
+    type Future =
+        PreparedServiceFuture<<F::Item as Service>::Future, F::Error>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        let future = match self.state {
+            PreparedServiceState::Ready(ref service) => {
+                Some(service.call(request))
+            }
+            _ => None,
+        };
+
+        PreparedServiceFuture {
+            future,
+            _preparation_error: PhantomData,
+        }
+    }
+}
+
+impl<F> FiniteService for PreparedService<F>
+where
+    F: Future,
+    F::Item: FiniteService,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        match self.state {
+            PreparedServiceState::Preparing(_) => Ok(false),
+            PreparedServiceState::Ready(ref service) => service
+                .has_finished()
+                .map_err(PreparedServiceError::ServiceError),
+            PreparedServiceState::Stopped => Ok(true),
+        }
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        if let PreparedServiceState::Ready(ref mut service) = self.state {
+            return service
+                .force_stop()
+                .map_err(PreparedServiceError::ServiceError);
+        }
+
+        self.state = PreparedServiceState::Stopped;
+
+        Ok(())
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        let mut service = match self.state {
+            PreparedServiceState::Preparing(ref mut service_future) => {
+                try_ready!(
+                    service_future
+                        .poll()
+                        .map_err(PreparedServiceError::PreparationError)
+                )
+            }
+            _ => return Ok(Async::Ready(())),
+        };
+
+        if let Some(context) = self.context.take() {
+            service
+                .connected(&context)
+                .map_err(PreparedServiceError::ServiceError)?;
+        }
+
+        self.state = PreparedServiceState::Ready(service);
+
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        match self.state {
+            PreparedServiceState::Ready(ref mut service) => {
+                service
+                    .poll_ready()
+                    .map_err(PreparedServiceError::ServiceError)
+            }
+            _ => Ok(Async::Ready(())),
+        }
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        match self.state {
+            PreparedServiceState::Preparing(_) => Ok(Async::NotReady),
+            PreparedServiceState::Ready(ref mut service) => {
+                service
+                    .poll_finished()
+                    .map_err(PreparedServiceError::ServiceError)
+            }
+            PreparedServiceState::Stopped => Ok(Async::Ready(())),
+        }
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        match self.state {
+            PreparedServiceState::Ready(ref mut service) => {
+                service
+                    .connected(context)
+                    .map_err(PreparedServiceError::ServiceError)
+            }
+            _ => {
+                self.context = Some(*context);
+                Ok(())
+            }
+        }
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        match self.state {
+            PreparedServiceState::Ready(ref mut service) => {
+                service.push_receiver()
+            }
+            _ => None,
+        }
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        match self.state {
+            PreparedServiceState::Ready(ref mut service) => {
+                service
+                    .request_stop()
+                    .map_err(PreparedServiceError::ServiceError)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        match self.state {
+            PreparedServiceState::Ready(ref mut service) => service
+                .poll_stop()
+                .map_err(PreparedServiceError::ServiceError),
+            _ => self.force_stop().map(Async::Ready),
+        }
+    }
+}
+
+pub struct PreparedServiceFuture<T, E> {
+    future: Option<T>,
+    _preparation_error: PhantomData<E>,
+}
+
+impl<T, E> Future for PreparedServiceFuture<T, E>
+where
+    T: Future,
+{
+    type Item = T::Item;
+    type Error = PreparedServiceError<E, T::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.future {
+            Some(ref mut future) => {
+                future.poll().map_err(PreparedServiceError::ServiceError)
+            }
+            None => Err(PreparedServiceError::CalledBeforePrepared),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+    use tokio_service::Service;
+
+    use super::super::super::async_server_error::AsyncServerError;
+    use super::super::super::termination_reason::TerminationReason;
+    use super::super::super::testing::{
+        active_server, CallLog, MockFuture, MockResponse, MockService,
+        ScriptedTransport,
+    };
+    use super::super::prepared_service_error::PreparedServiceError;
+    use super::PreparedService;
+
+    type Preparation = MockFuture<MockService<u32, u32, ()>, &'static str>;
+
+    fn prepared_after(
+        polls: usize,
+        service: MockService<u32, u32, ()>,
+    ) -> PreparedService<Preparation> {
+        let response = MockResponse::Delay(service, polls);
+
+        PreparedService::new(MockFuture::new(response))
+    }
+
+    #[test]
+    fn requests_are_served_once_prepared() {
+        let call_log = CallLog::new();
+        let service = MockService::new()
+            .respond(10)
+            .finish_after(1)
+            .with_call_log(call_log.clone());
+        let transport = ScriptedTransport::<_, _, ()>::new(call_log.clone())
+            .request(1)
+            .keep_open();
+
+        let server = active_server(prepared_after(2, service), transport);
+        let reason = server.wait().unwrap();
+
+        assert_eq!(reason, TerminationReason::ServiceFinished);
+        assert_eq!(call_log.responses(), vec![10]);
+    }
+
+    #[test]
+    fn calls_before_preparation_fail() {
+        let service = prepared_after(1, MockService::new().respond(10));
+
+        match service.call(1).wait() {
+            Err(PreparedServiceError::CalledBeforePrepared) => {}
+            result => panic!("unexpected call result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn failed_preparation_ends_the_session() {
+        let preparation = MockFuture::new(MockResponse::Fail("unavailable"));
+        let service: PreparedService<Preparation> =
+            PreparedService::new(preparation);
+        let transport =
+            ScriptedTransport::<_, _, ()>::new(CallLog::new()).request(1);
+
+        match active_server(service, transport).wait() {
+            Err(AsyncServerError::ServicePreparationError(
+                PreparedServiceError::PreparationError("unavailable"),
+            )) => {}
+            result => panic!("unexpected session result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn service_errors_are_wrapped() {
+        let service = MockService::new().fail(());
+        let transport =
+            ScriptedTransport::<_, _, ()>::new(CallLog::new()).request(1);
+
+        match active_server(prepared_after(0, service), transport).wait() {
+            Err(AsyncServerError::NewResponseError(
+                PreparedServiceError::ServiceError(()),
+            )) => {}
+            result => panic!("unexpected session result: {:?}", result),
+        }
+    }
+}
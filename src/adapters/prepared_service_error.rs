@@ -0,0 +1,11 @@
+#[derive(Debug, Fail)]
+pub enum PreparedServiceError<F, S> {
+    #[fail(display = "service was called before it was prepared")]
+    CalledBeforePrepared,
+
+    #[fail(display = "failed to prepare the service")]
+    PreparationError(#[cause] F),
+
+    #[fail(display = "prepared service failed")]
+    ServiceError(#[cause] S),
+}
@@ -0,0 +1,169 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use futures::{Async, Poll};
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+
+pub(crate) struct SharedInstance<S> {
+    service: Option<S>,
+    stopped: bool,
+}
+
+impl<S> SharedInstance<S>
+where
+    S: FiniteService,
+{
+    pub(crate) fn new() -> Self {
+        SharedInstance {
+            service: None,
+            stopped: false,
+        }
+    }
+
+    pub(crate) fn is_created(&self) -> bool {
+        self.service.is_some()
+    }
+
+    pub(crate) fn is_live(&self) -> bool {
+        self.service.is_some() && !self.stopped
+    }
+
+    pub(crate) fn create(&mut self, service: S) {
+        self.service = Some(service);
+        self.stopped = false;
+    }
+
+    pub(crate) fn force_stop(&mut self) -> Result<(), S::Error> {
+        if self.stopped {
+            return Ok(());
+        }
+
+        match self.service {
+            Some(ref mut service) => {
+                self.stopped = true;
+                service.force_stop()
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn service(&mut self) -> &mut S {
+        self.service
+            .as_mut()
+            .expect("shared service used before it was created")
+    }
+}
+
+pub(crate) fn lock_instance<S>(
+    instance: &Mutex<SharedInstance<S>>,
+) -> MutexGuard<'_, SharedInstance<S>> {
+    instance.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub struct SharedService<S> {
+    instance: Arc<Mutex<SharedInstance<S>>>,
+}
+
+impl<S> SharedService<S>
+where
+    S: FiniteService,
+{
+    pub(crate) fn new(instance: Arc<Mutex<SharedInstance<S>>>) -> Self {
+        SharedService { instance }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SharedInstance<S>> {
+        lock_instance(&self.instance)
+    }
+}
+
+impl<S> Service for SharedService<S>
+where
+    S: FiniteService,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.lock().service().call(request)
+    }
+}
+
+impl<S> FiniteService for SharedService<S>
+where
+    S: FiniteService,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        let mut instance = self.lock();
+
+        if instance.stopped {
+            Ok(true)
+        } else {
+            instance.service().has_finished()
+        }
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.lock().force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.lock().service().poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.lock().service().poll_ready()
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        let mut instance = self.lock();
+
+        if instance.stopped {
+            Ok(Async::Ready(()))
+        } else {
+            instance.service().poll_finished()
+        }
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.lock().service().connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.lock().service().push_receiver()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        let mut instance = self.lock();
+
+        if instance.stopped {
+            Ok(())
+        } else {
+            instance.service().request_stop()
+        }
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        let mut instance = self.lock();
+
+        if instance.stopped {
+            return Ok(Async::Ready(()));
+        }
+
+        let poll_result = instance.service().poll_stop();
+
+        if let Ok(Async::Ready(())) = poll_result {
+            instance.stopped = true;
+        }
+
+        poll_result
+    }
+}
@@ -0,0 +1,211 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use tokio_service::NewService;
+
+use super::super::finite_service::FiniteService;
+use super::shared_service::{lock_instance, SharedInstance, SharedService};
+
+pub(crate) type SharedInstanceStop<E> =
+    Arc<dyn Fn() -> Result<(), E> + Send + Sync>;
+
+pub struct SharedServiceFactory<F>
+where
+    F: NewService,
+{
+    factory: F,
+    instance: Arc<Mutex<SharedInstance<F::Instance>>>,
+}
+
+impl<F> SharedServiceFactory<F>
+where
+    F: NewService,
+    F::Instance: FiniteService,
+{
+    pub fn new(factory: F) -> Self {
+        SharedServiceFactory {
+            factory,
+            instance: Arc::new(Mutex::new(SharedInstance::new())),
+        }
+    }
+
+    pub fn is_created(&self) -> bool {
+        lock_instance(&self.instance).is_created()
+    }
+
+    pub fn force_stop(&self) -> Result<(), F::Error> {
+        lock_instance(&self.instance).force_stop()
+    }
+
+    pub(crate) fn instance_stop(&self) -> SharedInstanceStop<F::Error>
+    where
+        F::Instance: Send + 'static,
+    {
+        let instance = self.instance.clone();
+
+        Arc::new(move || lock_instance(&instance).force_stop())
+    }
+}
+
+impl<F> Clone for SharedServiceFactory<F>
+where
+    F: NewService + Clone,
+{
+    fn clone(&self) -> Self {
+        SharedServiceFactory {
+            factory: self.factory.clone(),
+            instance: self.instance.clone(),
+        }
+    }
+}
+
+impl<F> NewService for SharedServiceFactory<F>
+where
+    F: NewService,
+    F::Instance: FiniteService,
+{
+    type Request = F::Request;
+    type Response = F::Response;
+    type Error = F::Error;
+    type Instance = SharedService<F::Instance>;
+
+    fn new_service(&self) -> io::Result<Self::Instance> {
+        let mut instance = lock_instance(&self.instance);
+
+        if !instance.is_live() {
+            instance.create(self.factory.new_service()?);
+        }
+
+        Ok(SharedService::new(self.instance.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::future::{self, FutureResult};
+    use tokio_service::{NewService, Service};
+
+    use super::super::super::finite_service::FiniteService;
+    use super::SharedServiceFactory;
+
+    struct CountedService {
+        stops: Arc<AtomicUsize>,
+    }
+
+    impl Service for CountedService {
+        type Request = ();
+        type Response = ();
+        type Error = io::Error;
+        type Future = FutureResult<(), io::Error>;
+
+        fn call(&self, _request: Self::Request) -> Self::Future {
+            future::ok(())
+        }
+    }
+
+    impl FiniteService for CountedService {
+        fn has_finished(&self) -> Result<bool, Self::Error> {
+            Ok(self.stops.load(Ordering::SeqCst) > 0)
+        }
+
+        fn force_stop(&mut self) -> Result<(), Self::Error> {
+            self.stops.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn counted_factory(
+        created: Arc<AtomicUsize>,
+        stops: Arc<AtomicUsize>,
+    ) -> impl Fn() -> io::Result<CountedService> + Clone {
+        move || {
+            created.fetch_add(1, Ordering::SeqCst);
+
+            Ok(CountedService {
+                stops: stops.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn shutdown_before_creation_stops_nothing() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let stops = Arc::new(AtomicUsize::new(0));
+        let factory = SharedServiceFactory::new(counted_factory(
+            created.clone(),
+            stops.clone(),
+        ));
+
+        factory.force_stop().unwrap();
+
+        assert!(!factory.is_created());
+        assert_eq!(created.load(Ordering::SeqCst), 0);
+        assert_eq!(stops.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn existing_instance_is_stopped_exactly_once() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let stops = Arc::new(AtomicUsize::new(0));
+        let factory = SharedServiceFactory::new(counted_factory(
+            created.clone(),
+            stops.clone(),
+        ));
+        let other_server_factory = factory.clone();
+
+        let mut first_session = factory.new_service().unwrap();
+        let mut second_session = other_server_factory.new_service().unwrap();
+
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+
+        first_session.force_stop().unwrap();
+        second_session.force_stop().unwrap();
+        factory.force_stop().unwrap();
+        (other_server_factory.instance_stop())().unwrap();
+
+        assert_eq!(stops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn instance_persists_across_sessions() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let stops = Arc::new(AtomicUsize::new(0));
+        let factory = SharedServiceFactory::new(counted_factory(
+            created.clone(),
+            stops.clone(),
+        ));
+
+        drop(factory.new_service().unwrap());
+        let session = factory.new_service().unwrap();
+
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+        assert!(!session.has_finished().unwrap());
+        assert_eq!(stops.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn stopped_instance_is_replaced_for_the_next_session() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let stops = Arc::new(AtomicUsize::new(0));
+        let factory = SharedServiceFactory::new(counted_factory(
+            created.clone(),
+            stops.clone(),
+        ));
+
+        factory.new_service().unwrap();
+        factory.force_stop().unwrap();
+
+        let mut session = factory.new_service().unwrap();
+
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+
+        session.force_stop().unwrap();
+        factory.force_stop().unwrap();
+
+        assert_eq!(stops.load(Ordering::SeqCst), 2);
+    }
+}
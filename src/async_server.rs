@@ -1,9 +1,9 @@
 use std::mem;
 use std::net::SocketAddr;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::time::SystemTime;
 
 use futures::{Async, Future, Poll, Stream};
-use tokio_core::net::TcpStream;
 use tokio_core::reactor::Handle;
 use tokio_proto::pipeline::ServerProto;
 use tokio_service::NewService;
@@ -12,15 +12,23 @@ use super::active_server::ActiveServer;
 use super::async_server_error::AsyncServerError;
 use super::finite_service::FiniteService;
 use super::listening_server::ListeningServer;
+use super::metered_stream::MeteredStream;
+use super::server_metrics::{MetricsSnapshot, ServerMetrics};
+use super::server_phase::ServerPhase;
+use super::server_state::ServerState;
+use super::session_id::SessionId;
+use super::session_observer::SessionObserver;
+use super::session_summary::SessionSummary;
+use super::termination_reason::TerminationReason;
 use super::start_server::StartServer;
 
-type Error<S: NewService, P: ServerProto<TcpStream>> =
+type Error<S: NewService, P: ServerProto<MeteredStream>> =
     AsyncServerError<S::Error, P::Error>;
 
 pub enum AsyncServer<S, P>
 where
     S: NewService<Request = P::Request>,
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S::Instance: FiniteService,
     P::Transport: Stream<Item = S::Request>,
 {
@@ -30,46 +38,127 @@ where
     ListenCancelled(ListeningServer<S, P>),
     Active(ActiveServer<S::Instance, P::Transport>),
     Disconnecting(ActiveServer<S::Instance, P::Transport>),
-    Dead,
+    Dead(Option<SessionSummary>),
 }
 
 impl<S, P> AsyncServer<S, P>
 where
     S: NewService<Request = P::Request, Response = P::Response>,
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S::Instance: FiniteService,
 {
-    pub fn new(
+    pub fn new<A>(
         address: SocketAddr,
         service_factory: S,
-        protocol: Arc<Mutex<P>>,
+        protocol: A,
         handle: Handle,
-    ) -> Self {
+    ) -> Self
+    where
+        A: Into<Arc<P>>,
+    {
         AsyncServer::Binding(
             StartServer::new(address, service_factory, protocol, handle),
         )
     }
 
-    pub fn shutdown(&mut self) -> Poll<(), Error<S, P>> {
+    pub fn metrics(&self) -> Option<MetricsSnapshot> {
+        self.server_metrics().map(|metrics| metrics.snapshot())
+    }
+
+    pub fn phase(&self) -> ServerPhase {
+        match *self {
+            AsyncServer::Binding(_) => ServerPhase::Binding,
+            AsyncServer::Listening(_) => ServerPhase::Listening,
+            AsyncServer::Active(ref handler) => {
+                if handler.is_prepared() {
+                    ServerPhase::Active
+                } else {
+                    ServerPhase::Preparing
+                }
+            }
+            AsyncServer::BindCancelled(_)
+            | AsyncServer::ListenCancelled(_)
+            | AsyncServer::Disconnecting(_) => ServerPhase::ShuttingDown,
+            AsyncServer::Dead(_) => ServerPhase::Finished,
+        }
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match *self {
+            AsyncServer::Listening(ref handler) => handler.local_addr(),
+            AsyncServer::ListenCancelled(ref handler) => handler.local_addr(),
+            AsyncServer::Active(ref handler) => Some(handler.local_addr()),
+            AsyncServer::Disconnecting(ref handler) => {
+                Some(handler.local_addr())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        match *self {
+            AsyncServer::Listening(ref handler) => handler.peer_addr(),
+            AsyncServer::ListenCancelled(ref handler) => handler.peer_addr(),
+            AsyncServer::Active(ref handler) => Some(handler.peer_addr()),
+            AsyncServer::Disconnecting(ref handler) => {
+                Some(handler.peer_addr())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn in_flight_requests(&self) -> usize {
+        match *self {
+            AsyncServer::Active(ref handler) => handler.in_flight_requests(),
+            AsyncServer::Disconnecting(ref handler) => {
+                handler.in_flight_requests()
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn state(&self) -> ServerState {
+        ServerState {
+            phase: self.phase(),
+            local_address: self.local_addr(),
+            peer_address: self.peer_addr(),
+            in_flight_requests: self.in_flight_requests(),
+        }
+    }
+
+    pub fn poll_bind(&mut self) -> Poll<SocketAddr, Error<S, P>> {
+        let poll_result = self.poll_listening();
+
+        if let Err(ref error) = poll_result {
+            self.report_error(error);
+        }
+
+        poll_result
+    }
+
+    pub fn shutdown(&mut self) -> Poll<SessionSummary, Error<S, P>> {
+        let session_id = self.session_id();
         let shutdown_result = match *self {
             AsyncServer::Binding(ref mut handler) => handler.shutdown(),
-            AsyncServer::BindCancelled(ref mut handler) => {
-                return handler.shutdown();
-            }
+            AsyncServer::BindCancelled(ref mut handler) => handler.shutdown(),
             AsyncServer::Listening(ref mut handler) => handler.shutdown(),
             AsyncServer::ListenCancelled(ref mut handler) => {
-                return handler.shutdown();
+                handler.shutdown()
             }
             AsyncServer::Active(ref mut handler) => handler.shutdown(),
-            AsyncServer::Disconnecting(ref mut handler) => {
-                return handler.shutdown();
-            }
-            AsyncServer::Dead => Ok(Async::Ready(())),
+            AsyncServer::Disconnecting(ref mut handler) => handler.shutdown(),
+            AsyncServer::Dead(ref summary) => return Self::outcome(summary),
         };
 
+        let shutdown_result = self.summarize(shutdown_result.map(|poll| {
+            poll.map(|()| TerminationReason::ShutdownRequested)
+        }));
+
+        self.report_result(&shutdown_result);
+
         let new_state = match shutdown_result {
             Ok(Async::NotReady) => {
-                match mem::replace(self, AsyncServer::Dead) {
+                match mem::replace(self, AsyncServer::Dead(None)) {
                     AsyncServer::Binding(handler) => {
                         AsyncServer::BindCancelled(handler)
                     }
@@ -79,23 +168,252 @@ where
                     AsyncServer::Active(handler) => {
                         AsyncServer::Disconnecting(handler)
                     }
-                    AsyncServer::Dead => AsyncServer::Dead,
                     shutting_down_state => shutting_down_state,
                 }
             }
-            _ => AsyncServer::Dead,
+            Ok(Async::Ready(ref summary)) => {
+                AsyncServer::Dead(Some(summary.clone()))
+            }
+            Err(_) => AsyncServer::Dead(None),
         };
 
         mem::replace(self, new_state);
+        self.log_state(session_id);
 
         shutdown_result
     }
+
+    fn outcome(
+        summary: &Option<SessionSummary>,
+    ) -> Poll<SessionSummary, Error<S, P>> {
+        match *summary {
+            Some(ref summary) => Ok(Async::Ready(summary.clone())),
+            None => Err(AsyncServerError::ServerWasShutDown),
+        }
+    }
+
+    fn session_id(&self) -> Option<SessionId> {
+        match *self {
+            AsyncServer::Binding(ref handler) => Some(handler.session_id()),
+            AsyncServer::BindCancelled(ref handler) => {
+                Some(handler.session_id())
+            }
+            AsyncServer::Listening(ref handler) => Some(handler.session_id()),
+            AsyncServer::ListenCancelled(ref handler) => {
+                Some(handler.session_id())
+            }
+            AsyncServer::Active(ref handler) => Some(handler.session_id()),
+            AsyncServer::Disconnecting(ref handler) => {
+                Some(handler.session_id())
+            }
+            AsyncServer::Dead(_) => None,
+        }
+    }
+
+    fn log_state(&self, session_id: Option<SessionId>) {
+        let session_id = match session_id {
+            Some(session_id) => session_id,
+            None => return,
+        };
+
+        match *self {
+            AsyncServer::Binding(_) => {
+                debug!("session {}: binding", session_id);
+            }
+            AsyncServer::BindCancelled(_) => {
+                info!("session {}: cancelling bind", session_id);
+            }
+            AsyncServer::Listening(ref handler) => match handler.local_addr() {
+                Some(local_address) => info!(
+                    "session {}: listening on {}",
+                    session_id, local_address
+                ),
+                None => info!("session {}: listening", session_id),
+            },
+            AsyncServer::ListenCancelled(_) => {
+                info!("session {}: cancelling listen", session_id);
+            }
+            AsyncServer::Active(ref handler) => {
+                info!(
+                    "session {}: active, connected to {}",
+                    session_id,
+                    handler.peer_addr()
+                );
+            }
+            AsyncServer::Disconnecting(ref handler) => {
+                info!(
+                    "session {}: disconnecting from {}",
+                    session_id,
+                    handler.peer_addr()
+                );
+            }
+            AsyncServer::Dead(_) => info!("session {}: dead", session_id),
+        }
+    }
+
+    fn server_metrics(&self) -> Option<&Arc<ServerMetrics>> {
+        match *self {
+            AsyncServer::Binding(ref handler) => Some(handler.metrics()),
+            AsyncServer::BindCancelled(ref handler) => Some(handler.metrics()),
+            AsyncServer::Listening(ref handler) => Some(handler.metrics()),
+            AsyncServer::ListenCancelled(ref handler) => {
+                Some(handler.metrics())
+            }
+            AsyncServer::Active(ref handler) => Some(handler.metrics()),
+            AsyncServer::Disconnecting(ref handler) => Some(handler.metrics()),
+            AsyncServer::Dead(_) => None,
+        }
+    }
+
+    fn observer(&self) -> Option<&SessionObserver<S::Error, P::Error>> {
+        match *self {
+            AsyncServer::Binding(ref handler) => Some(handler.observer()),
+            AsyncServer::BindCancelled(ref handler) => Some(handler.observer()),
+            AsyncServer::Listening(ref handler) => Some(handler.observer()),
+            AsyncServer::ListenCancelled(ref handler) => {
+                Some(handler.observer())
+            }
+            AsyncServer::Active(ref handler) => Some(handler.observer()),
+            AsyncServer::Disconnecting(ref handler) => {
+                Some(handler.observer())
+            }
+            AsyncServer::Dead(_) => None,
+        }
+    }
+
+    fn summarize(
+        &self,
+        result: Poll<TerminationReason, Error<S, P>>,
+    ) -> Poll<SessionSummary, Error<S, P>> {
+        let termination_reason = try_ready!(result);
+
+        match self.summary(termination_reason) {
+            Some(summary) => Ok(Async::Ready(summary)),
+            None => Err(AsyncServerError::ServerWasShutDown),
+        }
+    }
+
+    fn summary(
+        &self,
+        termination_reason: TerminationReason,
+    ) -> Option<SessionSummary> {
+        let observer = self.observer()?;
+
+        let (requests_received, responses_sent, messages_pushed) = match *self
+        {
+            AsyncServer::Active(ref handler) => (
+                handler.requests_received(),
+                handler.responses_sent(),
+                handler.messages_pushed(),
+            ),
+            AsyncServer::Disconnecting(ref handler) => (
+                handler.requests_received(),
+                handler.responses_sent(),
+                handler.messages_pushed(),
+            ),
+            _ => (0, 0, 0),
+        };
+
+        Some(SessionSummary {
+            session_id: observer.session_id(),
+            termination_reason,
+            peer_address: self.peer_addr(),
+            started_at: observer.started_at(),
+            finished_at: SystemTime::now(),
+            requests_received,
+            responses_sent,
+            messages_pushed,
+        })
+    }
+
+    fn report_result(&self, result: &Poll<SessionSummary, Error<S, P>>) {
+        match *result {
+            Ok(Async::Ready(ref summary)) => {
+                if let Some(observer) = self.observer() {
+                    observer.finished(summary.termination_reason);
+                }
+            }
+            Ok(Async::NotReady) => {}
+            Err(ref error) => self.report_error(error),
+        }
+    }
+
+    fn report_error(&self, error: &Error<S, P>) {
+        if let Some(session_id) = self.session_id() {
+            error!("session {}: {}", session_id, error.name());
+        }
+
+        if let Some(metrics) = self.server_metrics() {
+            metrics.error_occurred(error.name());
+        }
+
+        if let Some(observer) = self.observer() {
+            observer.error(error);
+            observer.finished(TerminationReason::Failed);
+        }
+    }
+
+    fn poll_listening(&mut self) -> Poll<SocketAddr, Error<S, P>> {
+        let mut listening_server = match *self {
+            AsyncServer::Binding(ref mut handler) => try_ready!(handler.poll()),
+            AsyncServer::Listening(ref mut handler) => {
+                return handler.bound_address().map(Async::Ready);
+            }
+            AsyncServer::Active(ref handler) => {
+                return Ok(Async::Ready(handler.local_addr()));
+            }
+            AsyncServer::Dead(_) => {
+                return Err(AsyncServerError::ServerWasShutDown);
+            }
+            _ => return Err(AsyncServerError::ShuttingDown),
+        };
+
+        let session_id = listening_server.session_id();
+        let local_address = listening_server.bound_address()?;
+
+        mem::replace(self, AsyncServer::Listening(listening_server));
+        self.log_state(Some(session_id));
+
+        Ok(Async::Ready(local_address))
+    }
+
+    fn poll_state(&mut self) -> Poll<TerminationReason, Error<S, P>> {
+        let new_state = match *self {
+            AsyncServer::Binding(ref mut handler) => {
+                AsyncServer::Listening(try_ready!(handler.poll()))
+            }
+            AsyncServer::Listening(ref mut handler) => {
+                AsyncServer::Active(try_ready!(handler.poll()))
+            }
+            AsyncServer::Active(ref mut handler) => {
+                let termination_reason = try_ready!(handler.poll());
+
+                info!(
+                    "session {}: finished ({:?})",
+                    handler.session_id(),
+                    termination_reason
+                );
+
+                return Ok(Async::Ready(termination_reason));
+            }
+            AsyncServer::Dead(_) => {
+                return Err(AsyncServerError::ServerWasShutDown);
+            }
+            _ => return Err(AsyncServerError::ShuttingDown),
+        };
+
+        let session_id = self.session_id();
+
+        mem::replace(self, new_state);
+        self.log_state(session_id);
+        self.poll_state()
+    }
 }
 
 impl<S, P> From<StartServer<S, P>> for AsyncServer<S, P>
 where
     S: NewService<Request = P::Request, Response = P::Response>,
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S::Instance: FiniteService,
 {
     fn from(start_server: StartServer<S, P>) -> Self {
@@ -106,7 +424,7 @@ where
 impl<S, P> From<ListeningServer<S, P>> for AsyncServer<S, P>
 where
     S: NewService<Request = P::Request, Response = P::Response>,
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S::Instance: FiniteService,
 {
     fn from(listening_server: ListeningServer<S, P>) -> Self {
@@ -117,7 +435,7 @@ where
 impl<S, P> From<ActiveServer<S::Instance, P::Transport>> for AsyncServer<S, P>
 where
     S: NewService<Request = P::Request, Response = P::Response>,
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S::Instance: FiniteService,
 {
     fn from(active_server: ActiveServer<S::Instance, P::Transport>) -> Self {
@@ -128,35 +446,29 @@ where
 impl<S, P> Future for AsyncServer<S, P>
 where
     S: NewService<Request = P::Request, Response = P::Response>,
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S::Instance: FiniteService,
 {
-    type Item = ();
+    type Item = SessionSummary;
     type Error = Error<S, P>;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        let maybe_new_state = match *self {
-            AsyncServer::Binding(ref mut handler) => {
-                Some(AsyncServer::Listening(try_ready!(handler.poll())))
-            }
-            AsyncServer::Listening(ref mut handler) => {
-                Some(AsyncServer::Active(try_ready!(handler.poll())))
-            }
-            AsyncServer::Active(ref mut handler) => {
-                try_ready!(handler.poll());
-                None
-            }
-            AsyncServer::Dead => {
-                return Err(AsyncServerError::ServerWasShutDown);
-            }
-            _ => return Err(AsyncServerError::ShuttingDown),
-        };
+        if let AsyncServer::Dead(ref summary) = *self {
+            return Self::outcome(summary);
+        }
+
+        let poll_result = self.poll_state();
+        let poll_result = self.summarize(poll_result);
 
-        if let Some(new_state) = maybe_new_state {
-            mem::replace(self, new_state);
-            self.poll()
-        } else {
-            Ok(Async::Ready(()))
+        self.report_result(&poll_result);
+
+        if let Ok(Async::Ready(ref summary)) = poll_result {
+            let session_id = self.session_id();
+
+            mem::replace(self, AsyncServer::Dead(Some(summary.clone())));
+            self.log_state(session_id);
         }
+
+        poll_result
     }
 }
@@ -0,0 +1,301 @@
+use std::io;
+use std::net::{self, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_core::reactor::Handle;
+use tokio_proto::pipeline::ServerProto;
+use tokio_service::NewService;
+
+use super::adapters::{SharedInstanceStop, SharedServiceFactory};
+use super::async_server::AsyncServer;
+use super::async_server_error::AsyncServerError;
+use super::bind_listener::bind_listener;
+use super::bind_retry_policy::BindRetryPolicy;
+use super::error_policy::ErrorPolicy;
+use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::rate_limit_policy::{RateLimitPolicy, RateLimitRejection};
+use super::server_metrics::ServerMetrics;
+use super::server_observer::ServerObserver;
+use super::server_options::ServerOptions;
+use super::shutdown_mode::ShutdownMode;
+use super::start_server::StartServer;
+
+pub struct AsyncServerBuilder<S, P>
+where
+    S: NewService,
+    P: ServerProto<MeteredStream>,
+{
+    addresses: Vec<SocketAddr>,
+    listener: Option<Arc<net::TcpListener>>,
+    service_factory: Option<S>,
+    protocol: Option<Arc<P>>,
+    options: ServerOptions,
+    observer: Option<Arc<dyn ServerObserver<S::Error, P::Error>>>,
+    rate_limit_rejection: Option<RateLimitRejection<S::Request, S::Response>>,
+    shared_instance_stop: Option<SharedInstanceStop<S::Error>>,
+}
+
+impl<S, P> AsyncServerBuilder<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    pub fn new() -> Self {
+        AsyncServerBuilder {
+            addresses: Vec::new(),
+            listener: None,
+            service_factory: None,
+            protocol: None,
+            options: ServerOptions::default(),
+            observer: None,
+            rate_limit_rejection: None,
+            shared_instance_stop: None,
+        }
+    }
+
+    pub fn address(mut self, address: SocketAddr) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    pub fn addresses<I>(mut self, addresses: I) -> Self
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        self.addresses.extend(addresses);
+        self
+    }
+
+    pub fn listener(mut self, listener: net::TcpListener) -> Self {
+        self.listener = Some(Arc::new(listener));
+        self
+    }
+
+    pub fn service_factory(mut self, service_factory: S) -> Self {
+        self.service_factory = Some(service_factory);
+        self
+    }
+
+    pub fn shared_instance(
+        self,
+    ) -> AsyncServerBuilder<SharedServiceFactory<S>, P>
+    where
+        S::Instance: Send + 'static,
+    {
+        let service_factory =
+            self.service_factory.map(SharedServiceFactory::new);
+        let shared_instance_stop = service_factory
+            .as_ref()
+            .map(SharedServiceFactory::instance_stop);
+
+        AsyncServerBuilder {
+            addresses: self.addresses,
+            listener: self.listener,
+            service_factory,
+            protocol: self.protocol,
+            options: self.options,
+            observer: self.observer,
+            rate_limit_rejection: self.rate_limit_rejection,
+            shared_instance_stop,
+        }
+    }
+
+    pub fn protocol<A>(mut self, protocol: A) -> Self
+    where
+        A: Into<Arc<P>>,
+    {
+        self.protocol = Some(protocol.into());
+        self
+    }
+
+    pub fn accept_timeout(mut self, timeout: Duration) -> Self {
+        self.options.accept_timeout = Some(timeout);
+        self
+    }
+
+    pub fn accept_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.options.accept_retry_backoff = Some(backoff);
+        self
+    }
+
+    pub fn max_pending_requests(mut self, limit: usize) -> Self {
+        self.options.max_pending_requests = Some(limit);
+        self
+    }
+
+    pub fn rate_limit(mut self, policy: RateLimitPolicy) -> Self {
+        self.options.rate_limit = Some(policy);
+        self
+    }
+
+    pub fn reject_rate_limited<F>(mut self, rejection: F) -> Self
+    where
+        F: Fn(&S::Request) -> S::Response + Send + Sync + 'static,
+    {
+        self.rate_limit_rejection = Some(Arc::new(rejection));
+        self
+    }
+
+    pub fn nodelay(mut self, nodelay: bool) -> Self {
+        self.options.nodelay = Some(nodelay);
+        self
+    }
+
+    pub fn keepalive(mut self, keepalive: Duration) -> Self {
+        self.options.keepalive = Some(keepalive);
+        self
+    }
+
+    pub fn ttl(mut self, ttl: u32) -> Self {
+        self.options.ttl = Some(ttl);
+        self
+    }
+
+    pub fn reuse_port(mut self, reuse_port: bool) -> Self {
+        self.options.reuse_port = reuse_port;
+        self
+    }
+
+    pub fn bind_retry(mut self, policy: BindRetryPolicy) -> Self {
+        self.options.bind_retry = Some(policy);
+        self
+    }
+
+    pub fn shutdown_mode(mut self, shutdown_mode: ShutdownMode) -> Self {
+        self.options.shutdown_mode = shutdown_mode;
+        self
+    }
+
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.options.error_policy = policy;
+        self
+    }
+
+    pub fn metrics(mut self, metrics: Arc<ServerMetrics>) -> Self {
+        self.options.metrics = metrics;
+        self
+    }
+
+    pub fn observer(
+        mut self,
+        observer: Arc<dyn ServerObserver<S::Error, P::Error>>,
+    ) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn shared_metrics(&self) -> Arc<ServerMetrics> {
+        self.options.metrics.clone()
+    }
+
+    pub(crate) fn share_listener(mut self) -> io::Result<Self> {
+        if self.listener.is_none() {
+            let listener = bind_listener(&self.addresses, &self.options)?;
+
+            self.listener = Some(Arc::new(listener));
+        }
+
+        Ok(self)
+    }
+
+    pub fn build(
+        self,
+        handle: Handle,
+    ) -> Result<AsyncServer<S, P>, AsyncServerError<S::Error, P::Error>> {
+        if self.addresses.is_empty() && self.listener.is_none() {
+            return Err(AsyncServerError::MissingConfiguration("address"));
+        }
+
+        let rejects_without_limit = self.rate_limit_rejection.is_some()
+            && self.options.rate_limit.is_none();
+
+        if rejects_without_limit {
+            return Err(AsyncServerError::MissingConfiguration(
+                "rate limit policy",
+            ));
+        }
+
+        if let Some(policy) = self.options.rate_limit {
+            if policy.burst == 0 {
+                return Err(AsyncServerError::InvalidConfiguration(
+                    "rate limit burst",
+                ));
+            }
+
+            if policy.refill_interval == Duration::from_secs(0) {
+                return Err(AsyncServerError::InvalidConfiguration(
+                    "rate limit refill interval",
+                ));
+            }
+        }
+
+        let service_factory = self.service_factory.ok_or(
+            AsyncServerError::MissingConfiguration("service factory"),
+        )?;
+        let protocol = self.protocol
+            .ok_or(AsyncServerError::MissingConfiguration("protocol"))?;
+
+        let start_server = StartServer::with_options(
+            self.addresses,
+            service_factory,
+            protocol,
+            handle,
+            self.options,
+        );
+
+        let start_server = match self.listener {
+            Some(listener) => start_server.with_shared_listener(listener),
+            None => start_server,
+        };
+
+        let start_server = match self.observer {
+            Some(observer) => start_server.with_observer(observer),
+            None => start_server,
+        };
+
+        let start_server = match self.shared_instance_stop {
+            Some(stop) => start_server.with_shared_instance_stop(stop),
+            None => start_server,
+        };
+
+        Ok(AsyncServer::from(match self.rate_limit_rejection {
+            Some(rejection) => {
+                start_server.with_rate_limit_rejection(rejection)
+            }
+            None => start_server,
+        }))
+    }
+}
+
+impl<S, P> Default for AsyncServerBuilder<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, P> Clone for AsyncServerBuilder<S, P>
+where
+    S: NewService + Clone,
+    P: ServerProto<MeteredStream>,
+{
+    fn clone(&self) -> Self {
+        AsyncServerBuilder {
+            addresses: self.addresses.clone(),
+            listener: self.listener.clone(),
+            service_factory: self.service_factory.clone(),
+            protocol: self.protocol.clone(),
+            options: self.options.clone(),
+            observer: self.observer.clone(),
+            rate_limit_rejection: self.rate_limit_rejection.clone(),
+            shared_instance_stop: self.shared_instance_stop.clone(),
+        }
+    }
+}
@@ -4,6 +4,12 @@ use super::bound_connection_future::BindConnectionError;
 
 #[derive(Debug, Fail)]
 pub enum AsyncServerError<S, P> {
+    #[fail(display = "no connection was received before the accept timeout")]
+    AcceptTimedOut,
+
+    #[fail(display = "failed to wait for the accept timeout")]
+    AcceptTimerError(#[cause] io::Error),
+
     #[fail(display = "can't start server using the same future more than once")]
     AttemptToStartServerTwice,
 
@@ -13,6 +19,9 @@ pub enum AsyncServerError<S, P> {
     #[fail(display = "failed to bind connection into protocol transport")]
     BindError(#[cause] BindConnectionError<P>),
 
+    #[fail(display = "failed to wait before retrying to bind to socket")]
+    BindRetryTimerError(#[cause] io::Error),
+
     #[fail(display = "failed to flush responses in protocol transport")]
     FlushResponsesError(#[cause] P),
 
@@ -22,30 +31,118 @@ pub enum AsyncServerError<S, P> {
     #[fail(display = "StartServer can't shutdown server after it started")]
     IncorrectShutdownInStartServer,
 
+    #[fail(display = "server configuration has an invalid {}", _0)]
+    InvalidConfiguration(&'static str),
+
     #[fail(display = "ListeningServer can't be polled more than once")]
     ListenedTwice,
 
+    #[fail(display = "server configuration is missing the {}", _0)]
+    MissingConfiguration(&'static str),
+
     #[fail(display = "failed to get a new request from the protocol transport")]
     NewRequestError(#[cause] P),
 
     #[fail(display = "failed to get a response from the service")]
     NewResponseError(#[cause] S),
 
+    #[fail(display = "failed to wait for the rate limit to allow requests")]
+    RateLimitTimerError(#[cause] io::Error),
+
+    #[fail(display = "failed to create the reactor to run the server")]
+    ReactorCreationError(#[cause] io::Error),
+
+    #[fail(display = "failed to wait before restarting the server")]
+    RestartTimerError(#[cause] io::Error),
+
     #[fail(display = "failed to send response through protocol transport")]
     SendResponseError(#[cause] P),
 
     #[fail(display = "AsyncServer was shut down")]
     ServerWasShutDown,
 
+    #[fail(display = "thread running the server panicked")]
+    ServerThreadPanicked,
+
     #[fail(display = "service creation error")]
     ServiceCreationError(#[cause] io::Error),
 
     #[fail(display = "service failed when asked if it had finished")]
     ServiceFinishedCheckError(#[cause] S),
 
+    #[fail(display = "service failed while it was being prepared")]
+    ServicePreparationError(#[cause] S),
+
+    #[fail(display = "service failed when asked if it was ready")]
+    ServiceReadinessError(#[cause] S),
+
+    #[fail(display = "service failed to initialize for the connection")]
+    ServiceInitializationError(#[cause] S),
+
     #[fail(display = "service error")]
     ServiceShutdownError(#[cause] S),
 
     #[fail(display = "AsyncServer is shutting down")]
     ShuttingDown,
 }
+
+impl<S, P> AsyncServerError<S, P> {
+    pub fn name(&self) -> &'static str {
+        use self::AsyncServerError::*;
+
+        match *self {
+            AcceptTimedOut => "AcceptTimedOut",
+            AcceptTimerError(_) => "AcceptTimerError",
+            AttemptToStartServerTwice => "AttemptToStartServerTwice",
+            BindSocketError(_) => "BindSocketError",
+            BindError(_) => "BindError",
+            BindRetryTimerError(_) => "BindRetryTimerError",
+            FlushResponsesError(_) => "FlushResponsesError",
+            IncorrectShutdownInListeningServer => {
+                "IncorrectShutdownInListeningServer"
+            }
+            IncorrectShutdownInStartServer => "IncorrectShutdownInStartServer",
+            InvalidConfiguration(_) => "InvalidConfiguration",
+            ListenedTwice => "ListenedTwice",
+            MissingConfiguration(_) => "MissingConfiguration",
+            NewRequestError(_) => "NewRequestError",
+            NewResponseError(_) => "NewResponseError",
+            RateLimitTimerError(_) => "RateLimitTimerError",
+            ReactorCreationError(_) => "ReactorCreationError",
+            RestartTimerError(_) => "RestartTimerError",
+            SendResponseError(_) => "SendResponseError",
+            ServerWasShutDown => "ServerWasShutDown",
+            ServerThreadPanicked => "ServerThreadPanicked",
+            ServiceCreationError(_) => "ServiceCreationError",
+            ServiceFinishedCheckError(_) => "ServiceFinishedCheckError",
+            ServiceInitializationError(_) => "ServiceInitializationError",
+            ServicePreparationError(_) => "ServicePreparationError",
+            ServiceReadinessError(_) => "ServiceReadinessError",
+            ServiceShutdownError(_) => "ServiceShutdownError",
+            ShuttingDown => "ShuttingDown",
+        }
+    }
+
+    pub fn is_restartable(&self) -> bool {
+        use self::AsyncServerError::*;
+
+        match *self {
+            AcceptTimedOut
+            | AcceptTimerError(_)
+            | BindSocketError(_)
+            | BindError(_)
+            | BindRetryTimerError(_)
+            | FlushResponsesError(_)
+            | NewRequestError(_)
+            | NewResponseError(_)
+            | RateLimitTimerError(_)
+            | SendResponseError(_)
+            | ServiceCreationError(_)
+            | ServiceFinishedCheckError(_)
+            | ServiceInitializationError(_)
+            | ServicePreparationError(_)
+            | ServiceReadinessError(_) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::io;
+use std::net::{self, SocketAddr};
+
+use net2::TcpBuilder;
+
+use super::server_options::ServerOptions;
+
+pub fn bind_listener(
+    addresses: &[SocketAddr],
+    options: &ServerOptions,
+) -> io::Result<net::TcpListener> {
+    let mut last_error = None;
+
+    for address in addresses {
+        match bind_address(address, options) {
+            Ok(listener) => return Ok(listener),
+            Err(error) => last_error = Some(error),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "no address to bind to")
+    }))
+}
+
+fn bind_address(
+    address: &SocketAddr,
+    options: &ServerOptions,
+) -> io::Result<net::TcpListener> {
+    let builder = match *address {
+        SocketAddr::V4(_) => TcpBuilder::new_v4()?,
+        SocketAddr::V6(_) => TcpBuilder::new_v6()?,
+    };
+
+    configure_reuse(&builder, options)?;
+    builder.bind(address)?;
+    builder.listen(1024)
+}
+
+#[cfg(unix)]
+fn configure_reuse(
+    builder: &TcpBuilder,
+    options: &ServerOptions,
+) -> io::Result<()> {
+    use net2::unix::UnixTcpBuilderExt;
+
+    builder.reuse_address(true)?;
+
+    if options.reuse_port {
+        builder.reuse_port(true)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn configure_reuse(
+    _builder: &TcpBuilder,
+    options: &ServerOptions,
+) -> io::Result<()> {
+    if options.reuse_port {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "port reuse is not supported on this platform",
+        ))
+    } else {
+        Ok(())
+    }
+}
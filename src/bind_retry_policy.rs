@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BindRetryPolicy {
+    pub max_attempts: usize,
+    pub backoff: Duration,
+    pub deadline: Option<Duration>,
+}
+
+impl BindRetryPolicy {
+    pub fn new(max_attempts: usize, backoff: Duration) -> Self {
+        BindRetryPolicy {
+            max_attempts,
+            backoff,
+            deadline: None,
+        }
+    }
+
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
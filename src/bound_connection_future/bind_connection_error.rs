@@ -1,3 +1,5 @@
+use std::io;
+
 use super::super::connection_error::ConnectionError;
 
 #[derive(Debug, Fail)]
@@ -5,9 +7,62 @@ pub enum BindConnectionError<P> {
     #[fail(display = "bind connection failed")]
     BindError(#[cause] P),
 
+    #[fail(display = "failed to configure connection socket")]
+    ConfigureSocketError(#[cause] io::Error),
+
     #[fail(display = "no connection to bind")]
     NoConnectionToBind(#[cause] ConnectionError),
+}
+
+impl<P> BindConnectionError<P> {
+    pub fn transient_accept_error(&self) -> Option<&io::Error> {
+        match *self {
+            BindConnectionError::NoConnectionToBind(ref error) => {
+                error.transient_cause()
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::super::super::connection_error::ConnectionError;
+    use super::BindConnectionError;
+
+    type Error = BindConnectionError<io::Error>;
+
+    fn reset() -> io::Error {
+        io::Error::from(io::ErrorKind::ConnectionReset)
+    }
+
+    #[test]
+    fn transient_accept_errors_are_reported() {
+        let error: Error = BindConnectionError::NoConnectionToBind(
+            ConnectionError::FailedToReceiveConnection(reset()),
+        );
+
+        assert!(error.transient_accept_error().is_some());
+    }
+
+    #[test]
+    fn fatal_accept_errors_are_not_reported() {
+        let error: Error = BindConnectionError::NoConnectionToBind(
+            ConnectionError::NoConnectionsReceived,
+        );
+
+        assert!(error.transient_accept_error().is_none());
+    }
+
+    #[test]
+    fn errors_after_accepting_are_never_transient() {
+        let bind_error: Error = BindConnectionError::BindError(reset());
+        let configure_error: Error =
+            BindConnectionError::ConfigureSocketError(reset());
 
-    #[fail(display = "an other thread panicked with the protocol locked")]
-    ProtocolLockError,
+        assert!(bind_error.transient_accept_error().is_none());
+        assert!(configure_error.transient_accept_error().is_none());
+    }
 }
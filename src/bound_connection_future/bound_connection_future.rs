@@ -1,38 +1,61 @@
-use std::sync::{Arc, Mutex};
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use futures::{Future, Poll};
-use tokio_core::net::{TcpListener, TcpStream};
+use tokio_core::net::TcpListener;
 use tokio_proto::pipeline::ServerProto;
 
 use super::bind_connection_error::BindConnectionError;
 use super::state::State;
 use super::super::connection_future::ConnectionFuture;
+use super::super::metered_stream::MeteredStream;
+use super::super::server_options::ServerOptions;
+use super::super::session_id::SessionId;
 
 pub struct BoundConnectionFuture<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
     state: State<P>,
 }
 
 impl<P> BoundConnectionFuture<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
-    pub fn from(listener: TcpListener, protocol: Arc<Mutex<P>>) -> Self {
+    pub fn from(
+        listener: TcpListener,
+        protocol: Arc<P>,
+        options: ServerOptions,
+        session_id: SessionId,
+    ) -> Self {
         let connection = ConnectionFuture::from(listener);
 
         Self {
-            state: State::start_with(connection, protocol),
+            state: State::start_with(
+                connection,
+                protocol,
+                options,
+                session_id,
+            ),
         }
     }
 }
 
+impl<P> BoundConnectionFuture<P>
+where
+    P: ServerProto<MeteredStream>,
+{
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.state.peer_addr()
+    }
+}
+
 impl<P> Future for BoundConnectionFuture<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
-    type Item = P::Transport;
+    type Item = (P::Transport, SocketAddr);
     type Error = BindConnectionError<P::Error>;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
@@ -1,5 +1,6 @@
 use std::mem;
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use futures::{Async, Future, IntoFuture, Poll};
 use tokio_core::net::TcpStream;
@@ -7,10 +8,18 @@ use tokio_proto::pipeline::ServerProto;
 
 use super::bind_connection_error::BindConnectionError;
 use super::super::connection_future::ConnectionFuture;
+use super::super::metered_stream::MeteredStream;
+use super::super::server_options::ServerOptions;
+use super::super::session_id::SessionId;
+
+type BindPoll<P> = Poll<
+    (<P as ServerProto<MeteredStream>>::Transport, SocketAddr),
+    BindConnectionError<<P as ServerProto<MeteredStream>>::Error>,
+>;
 
 pub enum State<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
     Processing,
     WaitingForConnection(WaitForConnection<P>),
@@ -20,20 +29,30 @@ where
 
 impl<P> State<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
     pub fn start_with(
         connection: ConnectionFuture,
-        protocol: Arc<Mutex<P>>,
+        protocol: Arc<P>,
+        options: ServerOptions,
+        session_id: SessionId,
     ) -> Self {
-        let state_data = WaitForConnection::from(connection, protocol);
+        let state_data =
+            WaitForConnection::from(connection, protocol, options, session_id);
 
         State::WaitingForConnection(state_data)
     }
 
-    pub fn advance(
-        &mut self,
-    ) -> Poll<P::Transport, BindConnectionError<P::Error>> {
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        match *self {
+            State::WaitingForBindResult(ref handler) => {
+                Some(handler.peer_address)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn advance(&mut self) -> BindPoll<P> {
         let state = mem::replace(self, State::Processing);
 
         let (poll_result, new_state) = state.advance_to_new_state();
@@ -43,9 +62,7 @@ where
         poll_result
     }
 
-    fn advance_to_new_state(
-        self,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, Self) {
+    fn advance_to_new_state(self) -> (BindPoll<P>, Self) {
         match self {
             State::WaitingForConnection(handler) => handler.advance(),
             State::WaitingForBindResult(handler) => handler.advance(),
@@ -57,25 +74,34 @@ where
 
 pub struct WaitForConnection<P> {
     connection: ConnectionFuture,
-    protocol: Arc<Mutex<P>>,
+    protocol: Arc<P>,
+    options: ServerOptions,
+    session_id: SessionId,
 }
 
 impl<P> WaitForConnection<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
-    pub fn from(connection: ConnectionFuture, protocol: Arc<Mutex<P>>) -> Self {
+    pub fn from(
+        connection: ConnectionFuture,
+        protocol: Arc<P>,
+        options: ServerOptions,
+        session_id: SessionId,
+    ) -> Self {
         Self {
             connection,
             protocol,
+            options,
+            session_id,
         }
     }
 
-    fn advance(
-        mut self,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, State<P>) {
+    fn advance(mut self) -> (BindPoll<P>, State<P>) {
         match self.connection.poll() {
-            Ok(Async::Ready((socket, _))) => self.bind_connection(socket),
+            Ok(Async::Ready((socket, peer_address))) => {
+                self.bind_connection(socket, peer_address)
+            }
             Ok(Async::NotReady) => (Ok(Async::NotReady), self.same_state()),
             Err(connection_error) => {
                 let error =
@@ -89,24 +115,22 @@ where
     fn bind_connection(
         self,
         socket: TcpStream,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, State<P>) {
-        let result = if let Ok(protocol) = self.protocol.lock() {
-            Some(WaitForBindResult::advance_with(protocol, socket))
-        } else {
-            None
-        };
+        peer_address: SocketAddr,
+    ) -> (BindPoll<P>, State<P>) {
+        debug!(
+            "session {}: accepted connection from {}",
+            self.session_id, peer_address
+        );
+
+        if let Err(error) = self.options.configure_connection(&socket) {
+            let error = BindConnectionError::ConfigureSocketError(error);
 
-        if let Some(result) = result {
-            result
-        } else {
-            self.bind_connection_failure()
+            return (Err(error), self.same_state());
         }
-    }
 
-    fn bind_connection_failure(
-        self,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, State<P>) {
-        (Err(BindConnectionError::ProtocolLockError), self.same_state())
+        let stream = MeteredStream::new(socket, self.options.metrics.clone());
+
+        WaitForBindResult::advance_with(&self.protocol, stream, peer_address)
     }
 
     fn same_state(self) -> State<P> {
@@ -116,28 +140,31 @@ where
 
 pub struct WaitForBindResult<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
     bind_result: <P::BindTransport as IntoFuture>::Future,
+    peer_address: SocketAddr,
 }
 
 impl<P> WaitForBindResult<P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
 {
     fn advance_with(
-        protocol: MutexGuard<P>,
-        socket: TcpStream,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, State<P>) {
-        let bind_result = protocol.bind_transport(socket).into_future();
-        let bind_future = WaitForBindResult { bind_result };
+        protocol: &P,
+        stream: MeteredStream,
+        peer_address: SocketAddr,
+    ) -> (BindPoll<P>, State<P>) {
+        let bind_result = protocol.bind_transport(stream).into_future();
+        let bind_future = WaitForBindResult {
+            bind_result,
+            peer_address,
+        };
 
         bind_future.advance()
     }
 
-    fn advance(
-        mut self,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, State<P>) {
+    fn advance(mut self) -> (BindPoll<P>, State<P>) {
         match self.bind_result.poll() {
             Ok(Async::Ready(bound_connection)) => self.finish(bound_connection),
             Ok(Async::NotReady) => (Ok(Async::NotReady), self.same_state()),
@@ -150,8 +177,10 @@ where
     fn finish(
         self,
         connection: P::Transport,
-    ) -> (Poll<P::Transport, BindConnectionError<P::Error>>, State<P>) {
-        (Ok(Async::Ready(connection)), State::Finished)
+    ) -> (BindPoll<P>, State<P>) {
+        let bound_connection = (connection, self.peer_address);
+
+        (Ok(Async::Ready(bound_connection)), State::Finished)
     }
 
     fn same_state(self) -> State<P> {
@@ -0,0 +1,12 @@
+use std::net::SocketAddr;
+use std::time::SystemTime;
+
+use super::session_id::SessionId;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConnectionContext {
+    pub session_id: SessionId,
+    pub local_address: SocketAddr,
+    pub peer_address: SocketAddr,
+    pub accepted_at: SystemTime,
+}
@@ -1,5 +1,8 @@
 use std::io;
 
+#[cfg(unix)]
+use libc;
+
 #[derive(Debug, Fail)]
 pub enum ConnectionError {
     #[fail(display = "failed to receive a connection")]
@@ -8,3 +11,109 @@ pub enum ConnectionError {
     #[fail(display = "no connections were received")]
     NoConnectionsReceived,
 }
+
+impl ConnectionError {
+    pub fn transient_cause(&self) -> Option<&io::Error> {
+        match *self {
+            ConnectionError::FailedToReceiveConnection(ref error) => {
+                if is_transient(error) {
+                    Some(error)
+                } else {
+                    None
+                }
+            }
+            ConnectionError::NoConnectionsReceived => None,
+        }
+    }
+}
+
+fn is_transient(error: &io::Error) -> bool {
+    match error.kind() {
+        io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::Interrupted
+        | io::ErrorKind::TimedOut => true,
+        _ => is_resource_exhaustion(error),
+    }
+}
+
+#[cfg(unix)]
+fn is_resource_exhaustion(error: &io::Error) -> bool {
+    match error.raw_os_error() {
+        Some(libc::EMFILE)
+        | Some(libc::ENFILE)
+        | Some(libc::ENOBUFS)
+        | Some(libc::ENOMEM) => true,
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_resource_exhaustion(_error: &io::Error) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    #[cfg(unix)]
+    use libc;
+
+    use super::ConnectionError;
+
+    fn accept_error(kind: io::ErrorKind) -> ConnectionError {
+        ConnectionError::FailedToReceiveConnection(io::Error::from(kind))
+    }
+
+    #[test]
+    fn dropped_connections_are_transient() {
+        let kinds = [
+            io::ErrorKind::ConnectionAborted,
+            io::ErrorKind::ConnectionReset,
+            io::ErrorKind::Interrupted,
+            io::ErrorKind::TimedOut,
+        ];
+
+        for kind in &kinds {
+            let error = accept_error(*kind);
+            let cause = error.transient_cause().map(|cause| cause.kind());
+
+            assert_eq!(cause, Some(*kind));
+        }
+    }
+
+    #[test]
+    fn other_accept_errors_are_not_transient() {
+        let kinds = [
+            io::ErrorKind::PermissionDenied,
+            io::ErrorKind::InvalidInput,
+            io::ErrorKind::Other,
+        ];
+
+        for kind in &kinds {
+            assert!(accept_error(*kind).transient_cause().is_none());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resource_exhaustion_is_transient() {
+        let codes = [libc::EMFILE, libc::ENFILE, libc::ENOBUFS, libc::ENOMEM];
+
+        for code in &codes {
+            let error = ConnectionError::FailedToReceiveConnection(
+                io::Error::from_raw_os_error(*code),
+            );
+
+            assert!(error.transient_cause().is_some());
+        }
+    }
+
+    #[test]
+    fn missing_connection_is_not_transient() {
+        let error = ConnectionError::NoConnectionsReceived;
+
+        assert!(error.transient_cause().is_none());
+    }
+}
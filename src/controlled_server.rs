@@ -0,0 +1,118 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use futures::{Async, Future, Poll};
+use tokio_proto::pipeline::ServerProto;
+use tokio_service::NewService;
+
+use super::async_server::AsyncServer;
+use super::async_server_error::AsyncServerError;
+use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::server_phase::ServerPhase;
+use super::server_state::ServerState;
+use super::session_summary::SessionSummary;
+use super::shutdown_trigger::ShutdownSignal;
+
+pub struct ControlledServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    server: AsyncServer<S, P>,
+    shutdown_signal: ShutdownSignal,
+    shutting_down: bool,
+    shared_state: Option<Arc<Mutex<ServerState>>>,
+}
+
+impl<S, P> ControlledServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    pub fn new(
+        server: AsyncServer<S, P>,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        ControlledServer {
+            server,
+            shutdown_signal,
+            shutting_down: false,
+            shared_state: None,
+        }
+    }
+
+    pub fn with_shared_state(
+        mut self,
+        shared_state: Arc<Mutex<ServerState>>,
+    ) -> Self {
+        self.shared_state = Some(shared_state);
+        self.publish_state();
+        self
+    }
+
+    pub fn phase(&self) -> ServerPhase {
+        self.server.phase()
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.local_addr()
+    }
+
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.server.peer_addr()
+    }
+
+    pub fn in_flight_requests(&self) -> usize {
+        self.server.in_flight_requests()
+    }
+
+    pub fn state(&self) -> ServerState {
+        self.server.state()
+    }
+
+    fn publish_state(&self) {
+        if let Some(ref shared_state) = self.shared_state {
+            let state = self.server.state();
+
+            match shared_state.lock() {
+                Ok(mut shared_state) => *shared_state = state,
+                Err(poisoned) => *poisoned.into_inner() = state,
+            }
+        }
+    }
+
+    fn check_shutdown_signal(&mut self) {
+        if !self.shutting_down {
+            if let Ok(Async::Ready(())) = self.shutdown_signal.poll() {
+                self.shutting_down = true;
+            }
+        }
+    }
+}
+
+impl<S, P> Future for ControlledServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    type Item = SessionSummary;
+    type Error = AsyncServerError<S::Error, P::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.check_shutdown_signal();
+
+        let poll_result = if self.shutting_down {
+            self.server.shutdown()
+        } else {
+            self.server.poll()
+        };
+
+        self.publish_state();
+
+        poll_result
+    }
+}
@@ -0,0 +1,65 @@
+const DEFAULT_MAX_CONSECUTIVE_ERRORS: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorAction {
+    Fatal,
+    Ignore,
+    Retry,
+}
+
+impl Default for ErrorAction {
+    fn default() -> Self {
+        ErrorAction::Fatal
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ErrorPolicy {
+    pub request_decode: ErrorAction,
+    pub response: ErrorAction,
+    pub send: ErrorAction,
+    pub flush: ErrorAction,
+    pub finished_check: ErrorAction,
+    pub max_consecutive_errors: Option<usize>,
+}
+
+impl ErrorPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn consecutive_error_limit(&self) -> usize {
+        self.max_consecutive_errors
+            .unwrap_or(DEFAULT_MAX_CONSECUTIVE_ERRORS)
+    }
+
+    pub fn request_decode(mut self, action: ErrorAction) -> Self {
+        self.request_decode = action;
+        self
+    }
+
+    pub fn response(mut self, action: ErrorAction) -> Self {
+        self.response = action;
+        self
+    }
+
+    pub fn send(mut self, action: ErrorAction) -> Self {
+        self.send = action;
+        self
+    }
+
+    pub fn flush(mut self, action: ErrorAction) -> Self {
+        self.flush = action;
+        self
+    }
+
+    pub fn finished_check(mut self, action: ErrorAction) -> Self {
+        self.finished_check = action;
+        self
+    }
+
+    pub fn max_consecutive_errors(mut self, limit: usize) -> Self {
+        self.max_consecutive_errors = Some(limit);
+        self
+    }
+}
@@ -1,6 +1,55 @@
+use futures::{Async, Poll};
 use tokio_service::Service;
 
+use super::connection_context::ConnectionContext;
+use super::push_channel::PushReceiver;
+use super::stop_future::StopFuture;
+
 pub trait FiniteService: Service {
     fn has_finished(&self) -> Result<bool, <Self as Service>::Error>;
     fn force_stop(&mut self) -> Result<(), <Self as Service>::Error>;
+
+    fn poll_prepared(&mut self) -> Poll<(), <Self as Service>::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), <Self as Service>::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), <Self as Service>::Error> {
+        if self.has_finished()? {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+
+    fn connected(
+        &mut self,
+        _context: &ConnectionContext,
+    ) -> Result<(), <Self as Service>::Error> {
+        Ok(())
+    }
+
+    fn push_receiver(
+        &mut self,
+    ) -> Option<PushReceiver<<Self as Service>::Response>> {
+        None
+    }
+
+    fn request_stop(&mut self) -> Result<(), <Self as Service>::Error> {
+        Ok(())
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), <Self as Service>::Error> {
+        self.force_stop().map(Async::Ready)
+    }
+
+    fn stop(self) -> StopFuture<Self>
+    where
+        Self: Sized,
+    {
+        StopFuture::new(self)
+    }
 }
@@ -0,0 +1,147 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+const BUCKET_BOUNDS_IN_MICROSECONDS: [u64; 16] = [
+    50,
+    100,
+    250,
+    500,
+    1_000,
+    2_500,
+    5_000,
+    10_000,
+    25_000,
+    50_000,
+    100_000,
+    250_000,
+    500_000,
+    1_000_000,
+    2_500_000,
+    5_000_000,
+];
+
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    buckets: Vec<AtomicUsize>,
+    overflow: AtomicUsize,
+    count: AtomicU64,
+    total_microseconds: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub fn record(&self, latency: Duration) {
+        let microseconds = duration_in_microseconds(latency);
+        let bucket = BUCKET_BOUNDS_IN_MICROSECONDS
+            .iter()
+            .position(|&bound| microseconds <= bound);
+
+        match bucket {
+            Some(index) => self.buckets[index].fetch_add(1, Ordering::Relaxed),
+            None => self.overflow.fetch_add(1, Ordering::Relaxed),
+        };
+
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_microseconds
+            .fetch_add(microseconds, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        let buckets = BUCKET_BOUNDS_IN_MICROSECONDS
+            .iter()
+            .zip(self.buckets.iter())
+            .map(|(&bound, count)| {
+                (Duration::from_micros(bound), count.load(Ordering::Relaxed))
+            })
+            .collect();
+
+        let total_microseconds =
+            self.total_microseconds.load(Ordering::Relaxed);
+
+        LatencySnapshot {
+            buckets,
+            overflow: self.overflow.load(Ordering::Relaxed),
+            count: self.count.load(Ordering::Relaxed),
+            total: Duration::from_micros(total_microseconds),
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        LatencyHistogram {
+            buckets: BUCKET_BOUNDS_IN_MICROSECONDS
+                .iter()
+                .map(|_| AtomicUsize::new(0))
+                .collect(),
+            overflow: AtomicUsize::new(0),
+            count: AtomicU64::new(0),
+            total_microseconds: AtomicU64::new(0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LatencySnapshot {
+    pub buckets: Vec<(Duration, usize)>,
+    pub overflow: usize,
+    pub count: u64,
+    pub total: Duration,
+}
+
+impl LatencySnapshot {
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count > 0 {
+            let total_microseconds = duration_in_microseconds(self.total);
+
+            Some(Duration::from_micros(total_microseconds / self.count))
+        } else {
+            None
+        }
+    }
+}
+
+fn duration_in_microseconds(duration: Duration) -> u64 {
+    let seconds = duration.as_secs().saturating_mul(1_000_000);
+    let microseconds = u64::from(duration.subsec_nanos() / 1_000);
+
+    seconds.saturating_add(microseconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{LatencyHistogram, LatencySnapshot};
+
+    #[test]
+    fn latencies_are_sorted_into_buckets() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.record(Duration::from_micros(40));
+        histogram.record(Duration::from_micros(300));
+        histogram.record(Duration::from_secs(10));
+
+        let snapshot = histogram.snapshot();
+
+        assert_eq!(snapshot.buckets[0], (Duration::from_micros(50), 1));
+        assert_eq!(snapshot.buckets[3], (Duration::from_micros(500), 1));
+        assert_eq!(snapshot.overflow, 1);
+        assert_eq!(snapshot.count, 3);
+    }
+
+    #[test]
+    fn mean_of_an_empty_snapshot_is_none() {
+        assert_eq!(LatencySnapshot::default().mean(), None);
+    }
+
+    #[test]
+    fn mean_handles_counts_beyond_u32() {
+        let snapshot = LatencySnapshot {
+            count: 1 << 32,
+            total: Duration::from_secs(1 << 32),
+            ..LatencySnapshot::default()
+        };
+
+        assert_eq!(snapshot.mean(), Some(Duration::from_secs(1)));
+    }
+}
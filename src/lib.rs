@@ -3,24 +3,112 @@ extern crate failure;
 extern crate failure_derive;
 #[macro_use]
 extern crate futures;
+#[cfg(feature = "std-future")]
+extern crate futures03;
+#[cfg(unix)]
+extern crate libc;
+#[macro_use]
+extern crate log;
+extern crate net2;
+#[cfg(feature = "std-future")]
+extern crate tokio;
 extern crate tokio_core;
 extern crate tokio_io;
 extern crate tokio_proto;
 extern crate tokio_service;
+#[cfg(all(test, feature = "std-future"))]
+extern crate tokio_util;
 
 mod active_server;
+mod adapters;
 mod async_server;
+mod async_server_builder;
 mod async_server_error;
+mod bind_listener;
+mod bind_retry_policy;
 mod bound_connection_future;
+mod connection_context;
 mod connection_error;
 mod connection_future;
+mod controlled_server;
+mod error_policy;
 mod finite_service;
+mod latency_histogram;
 mod listening_server;
+mod metered_stream;
+mod middleware;
+mod push_channel;
+mod rate_limit_policy;
+mod rate_limiter;
+mod restart_event;
+mod restart_policy;
+mod serve;
+mod server_metrics;
+mod server_observer;
+mod server_options;
+mod server_phase;
+mod server_state;
+mod server_thread;
+mod server_threads;
+mod session_id;
+mod session_observer;
+mod session_summary;
+mod shutdown_mode;
+mod shutdown_trigger;
+mod spawn_server;
+mod spawn_servers;
 mod start_server;
 mod status;
+mod stop_future;
+mod supervisor;
+mod termination_reason;
+mod timed_request;
+mod token_bucket;
+
+#[cfg(feature = "std-future")]
+pub mod std_future;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
+pub use adapters::{
+    AsyncServiceFactory, FinishAfterDuration, FinishAfterRequests,
+    FinishOnRequest, FinishOnResponse, FinishOnResponseFuture, FinishOnSignal,
+    NeverFinishes, PreparedService, PreparedServiceError,
+    PreparedServiceFuture, SharedService, SharedServiceFactory,
+};
 pub use async_server::AsyncServer;
+pub use async_server_builder::AsyncServerBuilder;
 pub use async_server_error::AsyncServerError;
+pub use bind_retry_policy::BindRetryPolicy;
+pub use connection_context::ConnectionContext;
+pub use controlled_server::ControlledServer;
+pub use error_policy::{ErrorAction, ErrorPolicy};
 pub use finite_service::FiniteService;
+pub use latency_histogram::LatencySnapshot;
 pub use listening_server::ListeningServer;
+pub use metered_stream::MeteredStream;
+pub use middleware::{
+    ConcurrencyLimit, ConcurrencyLimitFuture, Logging, LoggingFuture,
+    MapRequest, MapResponse, RateLimit, Timing, TimingFuture,
+};
+pub use push_channel::{push_channel, PushReceiver, PushSender};
+pub use rate_limit_policy::{RateLimitPolicy, RateLimitRejection};
+pub use restart_event::{RestartEvent, RestartEvents};
+pub use restart_policy::RestartPolicy;
+pub use serve::serve;
+pub use server_metrics::{MetricsSnapshot, ServerMetrics};
+pub use server_observer::ServerObserver;
+pub use server_phase::ServerPhase;
+pub use server_state::ServerState;
+pub use server_thread::ServerThread;
+pub use server_threads::ServerThreads;
+pub use session_id::SessionId;
+pub use session_summary::SessionSummary;
+pub use shutdown_mode::ShutdownMode;
+pub use shutdown_trigger::{ShutdownSignal, ShutdownTrigger};
+pub use spawn_server::spawn_server;
+pub use spawn_servers::spawn_servers;
 pub use start_server::StartServer;
+pub use stop_future::StopFuture;
+pub use supervisor::Supervisor;
+pub use termination_reason::TerminationReason;
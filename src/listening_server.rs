@@ -1,67 +1,283 @@
 use std::io;
-use std::sync::{Arc, Mutex};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::SystemTime;
 
 use futures::{Async, Future, Poll};
-use tokio_core::net::{TcpListener, TcpStream};
+use tokio_core::net::TcpListener;
+use tokio_core::reactor::{Handle, Timeout};
 use tokio_proto::pipeline::ServerProto;
 use tokio_service::NewService;
 
 use super::active_server::ActiveServer;
+use super::adapters::SharedInstanceStop;
 use super::async_server_error::AsyncServerError;
-use super::bound_connection_future::BoundConnectionFuture;
+use super::bound_connection_future::{
+    BindConnectionError, BoundConnectionFuture,
+};
+use super::connection_context::ConnectionContext;
 use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::rate_limit_policy::RateLimitRejection;
+use super::rate_limiter::RateLimiter;
+use super::server_metrics::ServerMetrics;
+use super::server_options::ServerOptions;
+use super::session_id::SessionId;
+use super::session_observer::SessionObserver;
 
 pub struct ListeningServer<S, P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S: NewService,
 {
     connection: BoundConnectionFuture<P>,
-    new_service: Option<io::Result<S::Instance>>,
+    local_address: Option<SocketAddr>,
+    local_address_error: Option<io::Error>,
+    service_factory: Option<S>,
+    accept_timeout: Option<Timeout>,
+    accept_retry: Option<Timeout>,
+    handle: Option<Handle>,
+    options: ServerOptions,
+    observer: SessionObserver<S::Error, P::Error>,
+    rate_limit_rejection: Option<RateLimitRejection<S::Request, S::Response>>,
+    shared_instance_stop: Option<SharedInstanceStop<S::Error>>,
+    accepted_at: Option<SystemTime>,
 }
 
 impl<S, P> ListeningServer<S, P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S: NewService<Request = P::Request, Response = P::Response>,
     S::Instance: FiniteService,
 {
-    pub fn new(
+    pub fn new<A>(
         listener: TcpListener,
         service_factory: S,
-        protocol: Arc<Mutex<P>>,
+        protocol: A,
+    ) -> Self
+    where
+        A: Into<Arc<P>>,
+    {
+        let local_address = listener.local_addr();
+        let options = ServerOptions::default();
+        let observer = SessionObserver::new(SessionId::next(), None);
+
+        Self::with_options(
+            listener,
+            local_address,
+            service_factory,
+            protocol.into(),
+            options,
+            None,
+            None,
+            observer,
+        )
+    }
+
+    pub(crate) fn with_options(
+        listener: TcpListener,
+        local_address: io::Result<SocketAddr>,
+        service_factory: S,
+        protocol: Arc<P>,
+        options: ServerOptions,
+        accept_timeout: Option<Timeout>,
+        handle: Option<Handle>,
+        observer: SessionObserver<S::Error, P::Error>,
     ) -> Self {
+        let (local_address, local_address_error) = match local_address {
+            Ok(local_address) => (Some(local_address), None),
+            Err(error) => (None, Some(error)),
+        };
+
         ListeningServer {
-            new_service: Some(service_factory.new_service()),
-            connection: BoundConnectionFuture::from(listener, protocol),
+            service_factory: Some(service_factory),
+            connection: BoundConnectionFuture::from(
+                listener,
+                protocol,
+                options.clone(),
+                observer.session_id(),
+            ),
+            local_address,
+            local_address_error,
+            accept_timeout,
+            accept_retry: None,
+            handle,
+            options,
+            observer,
+            rate_limit_rejection: None,
+            shared_instance_stop: None,
+            accepted_at: None,
         }
     }
 
+    pub fn with_rate_limit_rejection(
+        mut self,
+        rejection: RateLimitRejection<S::Request, S::Response>,
+    ) -> Self {
+        self.rate_limit_rejection = Some(rejection);
+        self
+    }
+
+    pub(crate) fn with_shared_instance_stop(
+        mut self,
+        shared_instance_stop: SharedInstanceStop<S::Error>,
+    ) -> Self {
+        self.shared_instance_stop = Some(shared_instance_stop);
+        self
+    }
+
+    pub fn session_id(&self) -> SessionId {
+        self.observer.session_id()
+    }
+
+    pub fn observer(&self) -> &SessionObserver<S::Error, P::Error> {
+        &self.observer
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_address
+    }
+
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.connection.peer_addr()
+    }
+
+    pub fn metrics(&self) -> &Arc<ServerMetrics> {
+        &self.options.metrics
+    }
+
     pub fn shutdown(
         &mut self,
     ) -> Poll<(), AsyncServerError<S::Error, P::Error>> {
-        let mut service =
-            self.service(AsyncServerError::IncorrectShutdownInListeningServer)?;
+        match self.service_factory.take() {
+            Some(_) => self.stop_shared_instance(),
+            None => Err(AsyncServerError::IncorrectShutdownInListeningServer),
+        }
+    }
+
+    fn stop_shared_instance(
+        &mut self,
+    ) -> Poll<(), AsyncServerError<S::Error, P::Error>> {
+        if let Some(ref stop) = self.shared_instance_stop {
+            stop().map_err(AsyncServerError::ServiceShutdownError)?;
+        }
 
-        service.force_stop()
-            .map(Async::Ready)
-            .map_err(AsyncServerError::ServiceShutdownError)
+        Ok(Async::Ready(()))
     }
 
     fn service(
         &mut self,
-        empty_service_error: AsyncServerError<S::Error, P::Error>,
+        context: &ConnectionContext,
     ) -> Result<S::Instance, AsyncServerError<S::Error, P::Error>> {
-        let new_service_result =
-            self.new_service.take().ok_or(empty_service_error)?;
+        let service_factory = self
+            .service_factory
+            .take()
+            .ok_or(AsyncServerError::ListenedTwice)?;
+
+        let mut service = service_factory
+            .new_service()
+            .map_err(AsyncServerError::ServiceCreationError)?;
 
-        new_service_result.map_err(AsyncServerError::ServiceCreationError)
+        service
+            .connected(context)
+            .map_err(AsyncServerError::ServiceInitializationError)?;
+
+        Ok(service)
+    }
+
+    pub(crate) fn bound_address(
+        &mut self,
+    ) -> Result<SocketAddr, AsyncServerError<S::Error, P::Error>> {
+        match self.local_address {
+            Some(local_address) => Ok(local_address),
+            None => Err(match self.local_address_error.take() {
+                Some(error) => AsyncServerError::BindSocketError(error),
+                None => AsyncServerError::ListenedTwice,
+            }),
+        }
+    }
+
+    fn report_connection(&mut self, peer_address: SocketAddr) {
+        if self.accepted_at.is_none() {
+            self.accepted_at = Some(SystemTime::now());
+            self.observer.connected(peer_address);
+        }
+    }
+
+    fn check_accept_timeout(
+        &mut self,
+    ) -> Result<(), AsyncServerError<S::Error, P::Error>> {
+        if let Some(ref mut accept_timeout) = self.accept_timeout {
+            let poll_result = accept_timeout
+                .poll()
+                .map_err(AsyncServerError::AcceptTimerError)?;
+
+            if poll_result.is_ready() {
+                return Err(AsyncServerError::AcceptTimedOut);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn accept_retry_elapsed(
+        &mut self,
+    ) -> Result<bool, AsyncServerError<S::Error, P::Error>> {
+        let elapsed = match self.accept_retry {
+            Some(ref mut accept_retry) => accept_retry
+                .poll()
+                .map_err(AsyncServerError::AcceptTimerError)?
+                .is_ready(),
+            None => true,
+        };
+
+        if elapsed {
+            self.accept_retry = None;
+        }
+
+        Ok(elapsed)
+    }
+
+    fn retry_accept(
+        &mut self,
+        error: BindConnectionError<P::Error>,
+    ) -> Result<(), AsyncServerError<S::Error, P::Error>> {
+        let backoff = self.options.accept_retry_backoff();
+
+        // The backoff timer needs a reactor handle. Servers made with
+        // ListeningServer::new have none, so their accept errors stay fatal;
+        // servers started by StartServer always carry one.
+        let handle = match self.handle {
+            Some(ref handle) => handle.clone(),
+            None => return Err(AsyncServerError::BindError(error)),
+        };
+
+        match error.transient_accept_error() {
+            Some(cause) => {
+                warn!(
+                    "session {}: accept failed, retrying in {:?}: {}",
+                    self.session_id(),
+                    backoff,
+                    cause
+                );
+
+                self.observer.accept_retry(cause, backoff);
+            }
+            None => return Err(AsyncServerError::BindError(error)),
+        }
+
+        let accept_retry = Timeout::new(backoff, &handle)
+            .map_err(AsyncServerError::AcceptTimerError)?;
+
+        self.accept_retry = Some(accept_retry);
+
+        Ok(())
     }
 }
 
 impl<S, P> Future for ListeningServer<S, P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S: NewService<Request = P::Request, Response = P::Response>,
     S::Instance: FiniteService,
 {
@@ -69,12 +285,76 @@ where
     type Error = AsyncServerError<S::Error, P::Error>;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        let connection = try_ready!(
-            self.connection.poll().map_err(AsyncServerError::BindError)
+        let local_address = self.bound_address()?;
+
+        if !self.accept_retry_elapsed()? {
+            self.check_accept_timeout()?;
+
+            return Ok(Async::NotReady);
+        }
+
+        let poll_result = match self.connection.poll() {
+            Ok(poll_result) => poll_result,
+            Err(error) => {
+                self.retry_accept(error)?;
+
+                return self.poll();
+            }
+        };
+
+        let (connection, peer_address) = match poll_result {
+            Async::Ready(bound_connection) => bound_connection,
+            Async::NotReady => {
+                match self.connection.peer_addr() {
+                    Some(peer_address) => self.report_connection(peer_address),
+                    None => self.check_accept_timeout()?,
+                }
+
+                return Ok(Async::NotReady);
+            }
+        };
+
+        self.report_connection(peer_address);
+
+        debug!(
+            "session {}: protocol bound to connection from {}",
+            self.session_id(),
+            peer_address
         );
 
-        let service = self.service(AsyncServerError::ListenedTwice)?;
+        self.observer.transport_bound();
+
+        let context = ConnectionContext {
+            session_id: self.session_id(),
+            local_address,
+            peer_address,
+            accepted_at: self.accepted_at.unwrap_or_else(SystemTime::now),
+        };
+
+        let service = self.service(&context)?;
+        let options = self.options.clone();
+
+        let mut active_server = ActiveServer::with_options(
+            connection,
+            service,
+            local_address,
+            peer_address,
+            self.observer.clone(),
+            options,
+        );
+
+        if let (Some(policy), Some(handle)) =
+            (self.options.rate_limit, self.handle.as_ref())
+        {
+            let rate_limiter =
+                RateLimiter::new(policy.burst, policy.refill_interval, handle);
+
+            active_server = active_server.with_rate_limiter(
+                rate_limiter,
+                self.rate_limit_rejection.clone(),
+            );
+        }
 
-        Ok(Async::Ready(ActiveServer::new(connection, service)))
+        Ok(Async::Ready(active_server))
     }
 }
@@ -0,0 +1,104 @@
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use futures::Poll;
+use tokio_core::net::TcpStream;
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use super::server_metrics::ServerMetrics;
+
+pub struct MeteredStream {
+    stream: TcpStream,
+    metrics: Arc<ServerMetrics>,
+}
+
+impl MeteredStream {
+    pub(crate) fn new(stream: TcpStream, metrics: Arc<ServerMetrics>) -> Self {
+        MeteredStream { stream, metrics }
+    }
+
+    pub fn get_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut TcpStream {
+        &mut self.stream
+    }
+}
+
+impl Read for MeteredStream {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.stream.read(buffer)?;
+
+        self.metrics.bytes_read(bytes_read);
+
+        Ok(bytes_read)
+    }
+}
+
+impl Write for MeteredStream {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        let bytes_written = self.stream.write(buffer)?;
+
+        self.metrics.bytes_written(bytes_written);
+
+        Ok(bytes_written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl AsyncRead for MeteredStream {}
+
+impl AsyncWrite for MeteredStream {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        AsyncWrite::shutdown(&mut self.stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net;
+    use std::sync::Arc;
+
+    use futures::Stream;
+    use tokio_core::net::TcpListener;
+    use tokio_core::reactor::Core;
+    use tokio_io::io::{read_exact, write_all};
+
+    use super::MeteredStream;
+    use super::super::server_metrics::ServerMetrics;
+
+    #[test]
+    fn bytes_read_and_written_are_counted() {
+        let mut core = Core::new().unwrap();
+        let address = "127.0.0.1:0".parse().unwrap();
+        let listener = TcpListener::bind(&address, &core.handle()).unwrap();
+        let mut client =
+            net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (connection, _) = core.run(listener.incoming().into_future())
+            .map_err(|(error, _)| error)
+            .unwrap();
+        let (socket, _) = connection.unwrap();
+        let metrics = Arc::new(ServerMetrics::new());
+        let stream = MeteredStream::new(socket, metrics.clone());
+
+        client.write_all(b"ping").unwrap();
+
+        let (stream, _) = core.run(read_exact(stream, [0; 4])).unwrap();
+        core.run(write_all(stream, b"pong!")).unwrap();
+
+        let mut reply = [0; 5];
+
+        client.read_exact(&mut reply).unwrap();
+
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(&reply, b"pong!");
+        assert_eq!(snapshot.bytes_read, 4);
+        assert_eq!(snapshot.bytes_written, 5);
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::{Async, Future, Poll};
+use futures::task::AtomicTask;
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+
+struct InFlight {
+    requests: AtomicUsize,
+    waiting_task: AtomicTask,
+}
+
+pub struct ConcurrencyLimit<S> {
+    service: S,
+    limit: usize,
+    in_flight: Arc<InFlight>,
+}
+
+impl<S> ConcurrencyLimit<S> {
+    pub fn new(service: S, limit: usize) -> Self {
+        ConcurrencyLimit {
+            service,
+            limit,
+            in_flight: Arc::new(InFlight {
+                requests: AtomicUsize::new(0),
+                waiting_task: AtomicTask::new(),
+            }),
+        }
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.requests.load(Ordering::SeqCst)
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for ConcurrencyLimit<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ConcurrencyLimitFuture<S::Future>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.in_flight.requests.fetch_add(1, Ordering::SeqCst);
+
+        ConcurrencyLimitFuture {
+            future: self.service.call(request),
+            in_flight: self.in_flight.clone(),
+        }
+    }
+}
+
+impl<S> FiniteService for ConcurrencyLimit<S>
+where
+    S: FiniteService,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        self.service.has_finished()
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        try_ready!(self.service.poll_ready());
+
+        if self.in_flight() < self.limit {
+            return Ok(Async::Ready(()));
+        }
+
+        self.in_flight.waiting_task.register();
+
+        if self.in_flight() < self.limit {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_finished()
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.service.connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.service.push_receiver()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.request_stop()
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_stop()
+    }
+}
+
+pub struct ConcurrencyLimitFuture<F> {
+    future: F,
+    in_flight: Arc<InFlight>,
+}
+
+impl<F> Future for ConcurrencyLimitFuture<F>
+where
+    F: Future,
+{
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.future.poll()
+    }
+}
+
+impl<F> Drop for ConcurrencyLimitFuture<F> {
+    fn drop(&mut self) {
+        self.in_flight.requests.fetch_sub(1, Ordering::SeqCst);
+        self.in_flight.waiting_task.notify();
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use futures::{Async, Future, Poll};
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+
+pub struct Logging<S> {
+    service: S,
+    name: Arc<String>,
+}
+
+impl<S> Logging<S> {
+    pub fn new<N>(service: S, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Logging {
+            service,
+            name: Arc::new(name.into()),
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for Logging<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = LoggingFuture<S::Future>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        debug!("{}: request received", self.name);
+
+        LoggingFuture {
+            future: self.service.call(request),
+            name: self.name.clone(),
+        }
+    }
+}
+
+impl<S> FiniteService for Logging<S>
+where
+    S: FiniteService,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        self.service.has_finished()
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        info!("{}: stopping service", self.name);
+        self.service.force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_finished()
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.service.connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.service.push_receiver()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        info!("{}: requesting service to stop", self.name);
+        self.service.request_stop()
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_stop()
+    }
+}
+
+pub struct LoggingFuture<F> {
+    future: F,
+    name: Arc<String>,
+}
+
+impl<F> Future for LoggingFuture<F>
+where
+    F: Future,
+{
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let poll_result = self.future.poll();
+
+        match poll_result {
+            Ok(Async::Ready(_)) => debug!("{}: response ready", self.name),
+            Ok(Async::NotReady) => {}
+            Err(_) => warn!("{}: request failed", self.name),
+        }
+
+        poll_result
+    }
+}
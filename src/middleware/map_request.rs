@@ -0,0 +1,92 @@
+use std::marker::PhantomData;
+
+use futures::Poll;
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+
+pub struct MapRequest<S, F, R> {
+    service: S,
+    mapper: F,
+    _request: PhantomData<fn(R)>,
+}
+
+impl<S, F, R> MapRequest<S, F, R>
+where
+    S: Service,
+    F: Fn(R) -> S::Request,
+{
+    pub fn new(service: S, mapper: F) -> Self {
+        MapRequest {
+            service,
+            mapper,
+            _request: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S, F, R> Service for MapRequest<S, F, R>
+where
+    S: Service,
+    F: Fn(R) -> S::Request,
+{
+    type Request = R;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.service.call((self.mapper)(request))
+    }
+}
+
+impl<S, F, R> FiniteService for MapRequest<S, F, R>
+where
+    S: FiniteService,
+    F: Fn(R) -> S::Request,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        self.service.has_finished()
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_finished()
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.service.connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.service.push_receiver()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.request_stop()
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_stop()
+    }
+}
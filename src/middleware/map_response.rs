@@ -0,0 +1,92 @@
+use futures::{Future, Poll};
+use futures::future::Map;
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+
+pub struct MapResponse<S, F> {
+    service: S,
+    mapper: F,
+}
+
+impl<S, F> MapResponse<S, F> {
+    pub fn new(service: S, mapper: F) -> Self {
+        MapResponse { service, mapper }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S, F, R> Service for MapResponse<S, F>
+where
+    S: Service,
+    F: Fn(S::Response) -> R + Clone,
+{
+    type Request = S::Request;
+    type Response = R;
+    type Error = S::Error;
+    type Future = Map<S::Future, F>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.service.call(request).map(self.mapper.clone())
+    }
+}
+
+impl<S, F, R> FiniteService for MapResponse<S, F>
+where
+    S: FiniteService,
+    // Pushed messages are mapped inside a boxed `Send` stream, so mapping
+    // the responses of a finite service needs owned, sendable types. This
+    // is stricter than the `Service` impl above, so mappers that borrow or
+    // aren't `Send` can no longer wrap a `FiniteService`.
+    S::Response: Send + 'static,
+    F: Fn(S::Response) -> R + Clone + Send + 'static,
+    R: Send + 'static,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        self.service.has_finished()
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_finished()
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.service.connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        let mapper = self.mapper.clone();
+
+        self.service
+            .push_receiver()
+            .map(|receiver| receiver.map(mapper))
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.request_stop()
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_stop()
+    }
+}
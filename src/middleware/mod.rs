@@ -0,0 +1,13 @@
+mod concurrency_limit;
+mod logging;
+mod map_request;
+mod map_response;
+mod rate_limit;
+mod timing;
+
+pub use self::concurrency_limit::{ConcurrencyLimit, ConcurrencyLimitFuture};
+pub use self::logging::{Logging, LoggingFuture};
+pub use self::map_request::MapRequest;
+pub use self::map_response::MapResponse;
+pub use self::rate_limit::RateLimit;
+pub use self::timing::{Timing, TimingFuture};
@@ -0,0 +1,207 @@
+use std::io;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use futures::{Async, Poll};
+use tokio_core::reactor::Handle;
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+use super::super::rate_limiter::RateLimiter;
+
+pub struct RateLimit<S> {
+    service: S,
+    limiter: Mutex<RateLimiter>,
+    reserved: AtomicBool,
+}
+
+impl<S> RateLimit<S> {
+    pub fn new(
+        service: S,
+        burst: u32,
+        refill_interval: Duration,
+        handle: &Handle,
+    ) -> Self {
+        let limiter = RateLimiter::new(burst, refill_interval, handle);
+
+        RateLimit {
+            service,
+            limiter: Mutex::new(limiter),
+            reserved: AtomicBool::new(false),
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for RateLimit<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        // A token reserved by poll_ready pays for this request. Callers that
+        // skip poll_ready still take one here.
+        if !self.reserved.swap(false, Ordering::SeqCst) {
+            match self.limiter.lock() {
+                Ok(mut limiter) => limiter.acquire(),
+                Err(poisoned) => poisoned.into_inner().acquire(),
+            }
+        }
+
+        self.service.call(request)
+    }
+}
+
+impl<S> FiniteService for RateLimit<S>
+where
+    S: FiniteService,
+    S::Error: From<io::Error>,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        self.service.has_finished()
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        try_ready!(self.service.poll_ready());
+
+        if self.reserved.load(Ordering::SeqCst) {
+            return Ok(Async::Ready(()));
+        }
+
+        let limiter = match self.limiter.get_mut() {
+            Ok(limiter) => limiter,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        try_ready!(limiter.poll_ready().map_err(S::Error::from));
+
+        limiter.acquire();
+        self.reserved.store(true, Ordering::SeqCst);
+
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_finished()
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.service.connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.service.push_receiver()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.request_stop()
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_stop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use futures::{future, Async};
+    use tokio_core::reactor::Core;
+    use tokio_service::Service;
+
+    use super::super::super::finite_service::FiniteService;
+    use super::super::super::testing::MockService;
+    use super::RateLimit;
+
+    fn service(responses: u32) -> MockService<u32, u32, io::Error> {
+        (0..responses).fold(MockService::new(), |service, response| {
+            service.respond(response)
+        })
+    }
+
+    #[test]
+    fn ready_service_spends_one_token_per_request() {
+        let mut core = Core::new().unwrap();
+        let interval = Duration::from_secs(3600);
+        let mut service =
+            RateLimit::new(service(2), 2, interval, &core.handle());
+
+        let polls = core.run(future::lazy(|| {
+            let mut polls = Vec::new();
+
+            for request in 0..2 {
+                polls.push(service.poll_ready().unwrap());
+                service.call(request);
+            }
+
+            polls.push(service.poll_ready().unwrap());
+
+            Ok::<_, ()>(polls)
+        }));
+
+        assert_eq!(
+            polls.unwrap(),
+            vec![Async::Ready(()), Async::Ready(()), Async::NotReady]
+        );
+    }
+
+    #[test]
+    fn repeated_readiness_checks_reserve_a_single_token() {
+        let mut core = Core::new().unwrap();
+        let interval = Duration::from_secs(3600);
+        let mut service =
+            RateLimit::new(service(2), 2, interval, &core.handle());
+
+        let polls = core.run(future::lazy(|| {
+            let first = service.poll_ready().unwrap();
+            let second = service.poll_ready().unwrap();
+
+            service.call(0);
+
+            let third = service.poll_ready().unwrap();
+
+            Ok::<_, ()>(vec![first, second, third])
+        }));
+
+        assert_eq!(polls.unwrap(), vec![Async::Ready(()); 3]);
+    }
+
+    #[test]
+    fn unreserved_calls_still_spend_a_token() {
+        let mut core = Core::new().unwrap();
+        let interval = Duration::from_secs(3600);
+        let mut service =
+            RateLimit::new(service(1), 1, interval, &core.handle());
+
+        let ready = core.run(future::lazy(|| {
+            service.call(0);
+
+            Ok::<_, ()>(service.poll_ready().unwrap())
+        }));
+
+        assert_eq!(ready.unwrap(), Async::NotReady);
+    }
+}
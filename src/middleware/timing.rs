@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures::{Async, Future, Poll};
+use tokio_service::Service;
+
+use super::super::connection_context::ConnectionContext;
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+use super::super::latency_histogram::{LatencyHistogram, LatencySnapshot};
+
+pub struct Timing<S> {
+    service: S,
+    latency: Arc<LatencyHistogram>,
+}
+
+impl<S> Timing<S> {
+    pub fn new(service: S) -> Self {
+        Timing {
+            service,
+            latency: Arc::new(LatencyHistogram::default()),
+        }
+    }
+
+    pub fn latency(&self) -> LatencySnapshot {
+        self.latency.snapshot()
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S> Service for Timing<S>
+where
+    S: Service,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TimingFuture<S::Future>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        TimingFuture {
+            future: self.service.call(request),
+            started_at: Instant::now(),
+            latency: self.latency.clone(),
+        }
+    }
+}
+
+impl<S> FiniteService for Timing<S>
+where
+    S: FiniteService,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        self.service.has_finished()
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.force_stop()
+    }
+
+    fn poll_prepared(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_prepared()
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn poll_finished(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_finished()
+    }
+
+    fn connected(
+        &mut self,
+        context: &ConnectionContext,
+    ) -> Result<(), Self::Error> {
+        self.service.connected(context)
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.service.push_receiver()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        self.service.request_stop()
+    }
+
+    fn poll_stop(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_stop()
+    }
+}
+
+pub struct TimingFuture<F> {
+    future: F,
+    started_at: Instant,
+    latency: Arc<LatencyHistogram>,
+}
+
+impl<F> Future for TimingFuture<F>
+where
+    F: Future,
+{
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let poll_result = self.future.poll();
+
+        if let Ok(Async::NotReady) = poll_result {
+            return poll_result;
+        }
+
+        self.latency.record(self.started_at.elapsed());
+
+        poll_result
+    }
+}
@@ -0,0 +1,131 @@
+use futures::{Poll, Sink, StartSend, Stream};
+use futures::sync::mpsc::{self, SendError};
+
+pub fn push_channel<R>(buffer: usize) -> (PushSender<R>, PushReceiver<R>)
+where
+    R: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(buffer);
+
+    let sender = PushSender { sender };
+    let receiver = PushReceiver {
+        stream: Box::new(receiver),
+    };
+
+    (sender, receiver)
+}
+
+pub struct PushSender<R> {
+    sender: mpsc::Sender<R>,
+}
+
+impl<R> PushSender<R> {
+    pub fn poll_ready(&mut self) -> Poll<(), SendError<()>> {
+        self.sender.poll_ready()
+    }
+
+    pub fn try_push(&mut self, message: R) -> Result<(), R> {
+        self.sender
+            .try_send(message)
+            .map_err(|error| error.into_inner())
+    }
+
+    pub fn is_connected(&self) -> bool {
+        !self.sender.is_closed()
+    }
+}
+
+impl<R> Sink for PushSender<R> {
+    type SinkItem = R;
+    type SinkError = SendError<R>;
+
+    fn start_send(&mut self, message: R) -> StartSend<R, SendError<R>> {
+        self.sender.start_send(message)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<R>> {
+        self.sender.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<R>> {
+        self.sender.close()
+    }
+}
+
+impl<R> Clone for PushSender<R> {
+    fn clone(&self) -> Self {
+        PushSender {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+pub struct PushReceiver<R> {
+    stream: Box<dyn Stream<Item = R, Error = ()> + Send>,
+}
+
+impl<R> PushReceiver<R>
+where
+    R: Send + 'static,
+{
+    pub fn map<F, T>(self, mapper: F) -> PushReceiver<T>
+    where
+        F: FnMut(R) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        PushReceiver {
+            stream: Box::new(self.stream.map(mapper)),
+        }
+    }
+}
+
+impl<R> Stream for PushReceiver<R> {
+    type Item = R;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        self.stream.poll()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{Future, Sink, Stream};
+
+    use super::push_channel;
+
+    #[test]
+    fn full_channel_refuses_messages() {
+        let (mut sender, _receiver) = push_channel(1);
+
+        assert_eq!(sender.try_push(1), Ok(()));
+        assert_eq!(sender.try_push(2), Ok(()));
+        assert_eq!(sender.try_push(3), Err(3));
+    }
+
+    #[test]
+    fn sent_messages_are_received_in_order() {
+        let (sender, receiver) = push_channel(2);
+
+        let sender = sender.send(1).wait().unwrap();
+        let sender = sender.send(2).wait().unwrap();
+
+        drop(sender);
+
+        let messages = receiver.map(|message| message * 10).collect().wait();
+
+        assert_eq!(messages, Ok(vec![10, 20]));
+    }
+
+    #[test]
+    fn sender_sees_dropped_receiver() {
+        let (mut sender, receiver) = push_channel(1);
+
+        assert!(sender.is_connected());
+
+        drop(receiver);
+
+        assert!(!sender.is_connected());
+        assert_eq!(sender.try_push(1), Err(1));
+    }
+}
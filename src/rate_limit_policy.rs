@@ -0,0 +1,19 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type RateLimitRejection<Q, R> = Arc<dyn Fn(&Q) -> R + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimitPolicy {
+    pub burst: u32,
+    pub refill_interval: Duration,
+}
+
+impl RateLimitPolicy {
+    pub fn new(burst: u32, refill_interval: Duration) -> Self {
+        RateLimitPolicy {
+            burst,
+            refill_interval,
+        }
+    }
+}
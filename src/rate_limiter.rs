@@ -0,0 +1,55 @@
+use std::io;
+use std::time::Duration;
+
+use futures::{Async, Future, Poll};
+use tokio_core::reactor::{Handle, Timeout};
+
+use super::token_bucket::TokenBucket;
+
+pub struct RateLimiter {
+    bucket: TokenBucket,
+    handle: Handle,
+    timer: Option<Timeout>,
+}
+
+impl RateLimiter {
+    pub fn new(burst: u32, refill_interval: Duration, handle: &Handle) -> Self {
+        RateLimiter {
+            bucket: TokenBucket::new(burst, refill_interval),
+            handle: handle.clone(),
+            timer: None,
+        }
+    }
+
+    pub fn acquire(&mut self) {
+        self.bucket.acquire();
+    }
+
+    pub fn poll_ready(&mut self) -> Poll<(), io::Error> {
+        try_ready!(self.poll_timer());
+
+        let delay = self.bucket.time_until_available();
+
+        if delay == Duration::from_secs(0) {
+            Ok(Async::Ready(()))
+        } else {
+            self.wait_for(delay)
+        }
+    }
+
+    fn wait_for(&mut self, delay: Duration) -> Poll<(), io::Error> {
+        self.timer = Some(Timeout::new(delay, &self.handle)?);
+        self.poll_timer()
+    }
+
+    fn poll_timer(&mut self) -> Poll<(), io::Error> {
+        match self.timer {
+            Some(ref mut timer) => try_ready!(timer.poll()),
+            None => return Ok(Async::Ready(())),
+        }
+
+        self.timer = None;
+
+        Ok(Async::Ready(()))
+    }
+}
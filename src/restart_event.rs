@@ -0,0 +1,12 @@
+use std::time::Duration;
+
+use futures::sync::mpsc;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RestartEvent {
+    pub restart: usize,
+    pub error: &'static str,
+    pub backoff: Duration,
+}
+
+pub type RestartEvents = mpsc::UnboundedReceiver<RestartEvent>;
@@ -0,0 +1,111 @@
+use std::cmp;
+use std::time::Duration;
+
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 100;
+const DEFAULT_MAX_BACKOFF_MS: u64 = 30_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RestartPolicy {
+    pub max_restarts: Option<usize>,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_restarts(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = Some(max_restarts);
+        self
+    }
+
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn backoff_for(&self, restart: usize) -> Duration {
+        let no_backoff = Duration::from_secs(0);
+        let mut backoff = self.initial_backoff;
+
+        for _ in 1..restart {
+            if backoff >= self.max_backoff || backoff == no_backoff {
+                break;
+            }
+
+            backoff = backoff.checked_mul(2).unwrap_or(self.max_backoff);
+        }
+
+        cmp::min(backoff, self.max_backoff)
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy {
+            max_restarts: None,
+            initial_backoff: Duration::from_millis(DEFAULT_INITIAL_BACKOFF_MS),
+            max_backoff: Duration::from_millis(DEFAULT_MAX_BACKOFF_MS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RestartPolicy;
+
+    fn policy(initial_ms: u64, max_ms: u64) -> RestartPolicy {
+        RestartPolicy::new()
+            .initial_backoff(Duration::from_millis(initial_ms))
+            .max_backoff(Duration::from_millis(max_ms))
+    }
+
+    #[test]
+    fn backoff_doubles_for_each_restart() {
+        let policy = policy(100, 10_000);
+
+        assert_eq!(policy.backoff_for(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_for(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_for(4), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn backoff_is_capped_at_the_maximum() {
+        let policy = policy(100, 1_000);
+
+        assert_eq!(policy.backoff_for(5), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff_for(50), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn initial_backoff_above_the_maximum_is_capped() {
+        let policy = policy(5_000, 1_000);
+
+        assert_eq!(policy.backoff_for(1), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn doubling_past_the_largest_duration_is_capped() {
+        let policy = RestartPolicy::new()
+            .initial_backoff(Duration::from_secs(u64::MAX / 2 + 1))
+            .max_backoff(Duration::from_secs(u64::MAX));
+
+        assert_eq!(policy.backoff_for(3), Duration::from_secs(u64::MAX));
+    }
+
+    #[test]
+    fn zero_backoff_stays_zero() {
+        let policy = policy(0, 1_000);
+
+        assert_eq!(policy.backoff_for(usize::MAX), Duration::from_secs(0));
+    }
+}
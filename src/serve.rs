@@ -0,0 +1,24 @@
+use tokio_core::reactor::Core;
+use tokio_proto::pipeline::ServerProto;
+use tokio_service::NewService;
+
+use super::async_server_builder::AsyncServerBuilder;
+use super::async_server_error::AsyncServerError;
+use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::session_summary::SessionSummary;
+
+pub fn serve<S, P>(
+    builder: AsyncServerBuilder<S, P>,
+) -> Result<SessionSummary, AsyncServerError<S::Error, P::Error>>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    let mut core =
+        Core::new().map_err(AsyncServerError::ReactorCreationError)?;
+    let server = builder.build(core.handle())?;
+
+    core.run(server)
+}
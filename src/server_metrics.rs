@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use super::latency_histogram::{LatencyHistogram, LatencySnapshot};
+
+#[derive(Debug, Default)]
+pub struct ServerMetrics {
+    requests_received: AtomicUsize,
+    responses_sent: AtomicUsize,
+    pushed_messages: AtomicUsize,
+    in_flight_requests: AtomicUsize,
+    queued_responses: AtomicUsize,
+    flushes: AtomicUsize,
+    rate_limited_requests: AtomicUsize,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    errors: Mutex<BTreeMap<&'static str, usize>>,
+    request_latency: LatencyHistogram,
+}
+
+impl ServerMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let errors = match self.errors.lock() {
+            Ok(errors) => errors.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+
+        MetricsSnapshot {
+            requests_received: self.requests_received.load(Ordering::Relaxed),
+            responses_sent: self.responses_sent.load(Ordering::Relaxed),
+            pushed_messages: self.pushed_messages.load(Ordering::Relaxed),
+            in_flight_requests: self.in_flight_requests.load(Ordering::Relaxed),
+            queued_responses: self.queued_responses.load(Ordering::Relaxed),
+            flushes: self.flushes.load(Ordering::Relaxed),
+            rate_limited_requests: self.rate_limited_requests
+                .load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors,
+            request_latency: self.request_latency.snapshot(),
+        }
+    }
+
+    pub fn request_received(&self) {
+        self.requests_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn request_started(&self) {
+        self.in_flight_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn request_completed(&self, latency: Duration) {
+        self.in_flight_requests.fetch_sub(1, Ordering::Relaxed);
+        self.request_latency.record(latency);
+    }
+
+    pub fn response_queued(&self) {
+        self.queued_responses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn response_sent(&self) {
+        self.queued_responses.fetch_sub(1, Ordering::Relaxed);
+        self.responses_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn message_pushed(&self) {
+        self.pushed_messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn responses_flushed(&self) {
+        self.flushes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn request_rate_limited(&self) {
+        self.rate_limited_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn bytes_read(&self, bytes: usize) {
+        self.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn bytes_written(&self, bytes: usize) {
+        self.bytes_written.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn session_dropped(&self, in_flight_requests: usize, queued: usize) {
+        self.in_flight_requests
+            .fetch_sub(in_flight_requests, Ordering::Relaxed);
+        self.queued_responses.fetch_sub(queued, Ordering::Relaxed);
+    }
+
+    pub fn error_occurred(&self, error_name: &'static str) {
+        let mut errors = match self.errors.lock() {
+            Ok(errors) => errors,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        *errors.entry(error_name).or_insert(0) += 1;
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MetricsSnapshot {
+    pub requests_received: usize,
+    pub responses_sent: usize,
+    pub pushed_messages: usize,
+    pub in_flight_requests: usize,
+    pub queued_responses: usize,
+    pub flushes: usize,
+    pub rate_limited_requests: usize,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    pub errors: BTreeMap<&'static str, usize>,
+    pub request_latency: LatencySnapshot,
+}
@@ -0,0 +1,24 @@
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use super::async_server_error::AsyncServerError;
+use super::session_id::SessionId;
+use super::termination_reason::TerminationReason;
+
+pub trait ServerObserver<S, P>: Send + Sync {
+    fn on_bound(&self, _session: SessionId, _local_address: SocketAddr) {}
+    fn on_accept_retry(
+        &self,
+        _session: SessionId,
+        _error: &io::Error,
+        _backoff: Duration,
+    ) {
+    }
+    fn on_connected(&self, _session: SessionId, _peer_address: SocketAddr) {}
+    fn on_transport_bound(&self, _session: SessionId) {}
+    fn on_request(&self, _session: SessionId) {}
+    fn on_response(&self, _session: SessionId) {}
+    fn on_finished(&self, _session: SessionId, _reason: TerminationReason) {}
+    fn on_error(&self, _session: SessionId, _error: &AsyncServerError<S, P>) {}
+}
@@ -0,0 +1,53 @@
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_core::net::TcpStream;
+
+use super::bind_retry_policy::BindRetryPolicy;
+use super::error_policy::ErrorPolicy;
+use super::rate_limit_policy::RateLimitPolicy;
+use super::server_metrics::ServerMetrics;
+use super::shutdown_mode::ShutdownMode;
+
+const DEFAULT_ACCEPT_RETRY_BACKOFF_MS: u64 = 100;
+
+#[derive(Clone, Debug, Default)]
+pub struct ServerOptions {
+    pub accept_timeout: Option<Duration>,
+    pub accept_retry_backoff: Option<Duration>,
+    pub max_pending_requests: Option<usize>,
+    pub rate_limit: Option<RateLimitPolicy>,
+    pub nodelay: Option<bool>,
+    pub keepalive: Option<Duration>,
+    pub ttl: Option<u32>,
+    pub reuse_port: bool,
+    pub bind_retry: Option<BindRetryPolicy>,
+    pub shutdown_mode: ShutdownMode,
+    pub error_policy: ErrorPolicy,
+    pub metrics: Arc<ServerMetrics>,
+}
+
+impl ServerOptions {
+    pub fn accept_retry_backoff(&self) -> Duration {
+        self.accept_retry_backoff.unwrap_or_else(|| {
+            Duration::from_millis(DEFAULT_ACCEPT_RETRY_BACKOFF_MS)
+        })
+    }
+
+    pub fn configure_connection(&self, socket: &TcpStream) -> io::Result<()> {
+        if let Some(nodelay) = self.nodelay {
+            socket.set_nodelay(nodelay)?;
+        }
+
+        if self.keepalive.is_some() {
+            socket.set_keepalive(self.keepalive)?;
+        }
+
+        if let Some(ttl) = self.ttl {
+            socket.set_ttl(ttl)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ServerPhase {
+    Binding,
+    Listening,
+    Preparing,
+    Active,
+    ShuttingDown,
+    Finished,
+}
+
+impl Default for ServerPhase {
+    fn default() -> Self {
+        ServerPhase::Binding
+    }
+}
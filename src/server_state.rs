@@ -0,0 +1,11 @@
+use std::net::SocketAddr;
+
+use super::server_phase::ServerPhase;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ServerState {
+    pub phase: ServerPhase,
+    pub local_address: Option<SocketAddr>,
+    pub peer_address: Option<SocketAddr>,
+    pub in_flight_requests: usize,
+}
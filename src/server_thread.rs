@@ -0,0 +1,91 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use super::async_server_error::AsyncServerError;
+use super::server_metrics::{MetricsSnapshot, ServerMetrics};
+use super::server_phase::ServerPhase;
+use super::server_state::ServerState;
+use super::session_summary::SessionSummary;
+use super::shutdown_trigger::ShutdownTrigger;
+
+pub struct ServerThread<S, P> {
+    local_address: SocketAddr,
+    shutdown_trigger: ShutdownTrigger,
+    join_handle: JoinHandle<Result<SessionSummary, AsyncServerError<S, P>>>,
+    metrics: Arc<ServerMetrics>,
+    state: Arc<Mutex<ServerState>>,
+}
+
+impl<S, P> ServerThread<S, P> {
+    pub fn new(
+        local_address: SocketAddr,
+        shutdown_trigger: ShutdownTrigger,
+        join_handle: JoinHandle<Result<SessionSummary, AsyncServerError<S, P>>>,
+        metrics: Arc<ServerMetrics>,
+        state: Arc<Mutex<ServerState>>,
+    ) -> Self {
+        ServerThread {
+            local_address,
+            shutdown_trigger,
+            join_handle,
+            metrics,
+            state,
+        }
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    pub fn state(&self) -> ServerState {
+        match self.state.lock() {
+            Ok(state) => *state,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    pub fn phase(&self) -> ServerPhase {
+        self.state().phase
+    }
+
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.state().peer_address
+    }
+
+    pub fn in_flight_requests(&self) -> usize {
+        self.state().in_flight_requests
+    }
+
+    pub fn shutdown(self) -> Result<SessionSummary, AsyncServerError<S, P>> {
+        self.shutdown_trigger.shutdown();
+
+        Self::join_thread(self.join_handle)
+    }
+
+    pub fn join(self) -> Result<SessionSummary, AsyncServerError<S, P>> {
+        Self::join_thread(self.join_handle)
+    }
+
+    pub fn into_parts(
+        self,
+    ) -> (
+        JoinHandle<Result<SessionSummary, AsyncServerError<S, P>>>,
+        ShutdownTrigger,
+        SocketAddr,
+    ) {
+        (self.join_handle, self.shutdown_trigger, self.local_address)
+    }
+
+    fn join_thread(
+        join_handle: JoinHandle<Result<SessionSummary, AsyncServerError<S, P>>>,
+    ) -> Result<SessionSummary, AsyncServerError<S, P>> {
+        join_handle
+            .join()
+            .unwrap_or(Err(AsyncServerError::ServerThreadPanicked))
+    }
+}
@@ -0,0 +1,89 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use super::async_server_error::AsyncServerError;
+use super::server_metrics::{MetricsSnapshot, ServerMetrics};
+use super::server_state::ServerState;
+use super::server_thread::ServerThread;
+use super::session_summary::SessionSummary;
+use super::shutdown_trigger::ShutdownTrigger;
+
+type ServerResult<S, P> = Result<SessionSummary, AsyncServerError<S, P>>;
+
+pub struct ServerThreads<S, P> {
+    threads: Vec<ServerThread<S, P>>,
+    metrics: Arc<ServerMetrics>,
+}
+
+impl<S, P> ServerThreads<S, P> {
+    pub fn new(
+        threads: Vec<ServerThread<S, P>>,
+        metrics: Arc<ServerMetrics>,
+    ) -> Self {
+        ServerThreads { threads, metrics }
+    }
+
+    pub fn len(&self) -> usize {
+        self.threads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.threads.is_empty()
+    }
+
+    pub fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.threads.iter().map(ServerThread::local_addr).collect()
+    }
+
+    pub fn states(&self) -> Vec<ServerState> {
+        self.threads.iter().map(ServerThread::state).collect()
+    }
+
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    pub fn shutdown(self) -> Vec<ServerResult<S, P>> {
+        let (join_handles, shutdown_trigger) = self.into_parts();
+
+        shutdown_trigger.shutdown();
+
+        Self::join_threads(join_handles)
+    }
+
+    pub fn join(self) -> Vec<ServerResult<S, P>> {
+        let (join_handles, _) = self.into_parts();
+
+        Self::join_threads(join_handles)
+    }
+
+    pub fn into_parts(
+        self,
+    ) -> (Vec<JoinHandle<ServerResult<S, P>>>, ShutdownTrigger) {
+        let mut join_handles = Vec::with_capacity(self.threads.len());
+        let mut shutdown_triggers = Vec::with_capacity(self.threads.len());
+
+        for thread in self.threads {
+            let (join_handle, shutdown_trigger, _) = thread.into_parts();
+
+            join_handles.push(join_handle);
+            shutdown_triggers.push(shutdown_trigger);
+        }
+
+        (join_handles, shutdown_triggers.into_iter().collect())
+    }
+
+    fn join_threads(
+        join_handles: Vec<JoinHandle<ServerResult<S, P>>>,
+    ) -> Vec<ServerResult<S, P>> {
+        join_handles
+            .into_iter()
+            .map(|join_handle| {
+                join_handle
+                    .join()
+                    .unwrap_or(Err(AsyncServerError::ServerThreadPanicked))
+            })
+            .collect()
+    }
+}
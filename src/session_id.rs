@@ -0,0 +1,19 @@
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_SESSION_ID: AtomicUsize = AtomicUsize::new(1);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SessionId(usize);
+
+impl SessionId {
+    pub fn next() -> Self {
+        SessionId(NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Display for SessionId {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "#{}", self.0)
+    }
+}
@@ -0,0 +1,94 @@
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use super::async_server_error::AsyncServerError;
+use super::server_observer::ServerObserver;
+use super::session_id::SessionId;
+use super::termination_reason::TerminationReason;
+
+pub struct SessionObserver<S, P> {
+    session_id: SessionId,
+    started_at: SystemTime,
+    observer: Option<Arc<dyn ServerObserver<S, P>>>,
+}
+
+impl<S, P> SessionObserver<S, P> {
+    pub fn new(
+        session_id: SessionId,
+        observer: Option<Arc<dyn ServerObserver<S, P>>>,
+    ) -> Self {
+        SessionObserver {
+            session_id,
+            started_at: SystemTime::now(),
+            observer,
+        }
+    }
+
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    pub fn bound(&self, local_address: SocketAddr) {
+        if let Some(ref observer) = self.observer {
+            observer.on_bound(self.session_id, local_address);
+        }
+    }
+
+    pub fn accept_retry(&self, error: &io::Error, backoff: Duration) {
+        if let Some(ref observer) = self.observer {
+            observer.on_accept_retry(self.session_id, error, backoff);
+        }
+    }
+
+    pub fn connected(&self, peer_address: SocketAddr) {
+        if let Some(ref observer) = self.observer {
+            observer.on_connected(self.session_id, peer_address);
+        }
+    }
+
+    pub fn transport_bound(&self) {
+        if let Some(ref observer) = self.observer {
+            observer.on_transport_bound(self.session_id);
+        }
+    }
+
+    pub fn request(&self) {
+        if let Some(ref observer) = self.observer {
+            observer.on_request(self.session_id);
+        }
+    }
+
+    pub fn response(&self) {
+        if let Some(ref observer) = self.observer {
+            observer.on_response(self.session_id);
+        }
+    }
+
+    pub fn finished(&self, reason: TerminationReason) {
+        if let Some(ref observer) = self.observer {
+            observer.on_finished(self.session_id, reason);
+        }
+    }
+
+    pub fn error(&self, error: &AsyncServerError<S, P>) {
+        if let Some(ref observer) = self.observer {
+            observer.on_error(self.session_id, error);
+        }
+    }
+}
+
+impl<S, P> Clone for SessionObserver<S, P> {
+    fn clone(&self) -> Self {
+        SessionObserver {
+            session_id: self.session_id,
+            started_at: self.started_at,
+            observer: self.observer.clone(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime};
+
+use super::session_id::SessionId;
+use super::termination_reason::TerminationReason;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionSummary {
+    pub session_id: SessionId,
+    pub termination_reason: TerminationReason,
+    pub peer_address: Option<SocketAddr>,
+    pub started_at: SystemTime,
+    pub finished_at: SystemTime,
+    pub requests_received: usize,
+    pub responses_sent: usize,
+    pub messages_pushed: usize,
+}
+
+impl SessionSummary {
+    pub fn duration(&self) -> Duration {
+        self.finished_at
+            .duration_since(self.started_at)
+            .unwrap_or_default()
+    }
+}
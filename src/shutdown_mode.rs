@@ -0,0 +1,11 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShutdownMode {
+    Immediate,
+    Graceful,
+}
+
+impl Default for ShutdownMode {
+    fn default() -> Self {
+        ShutdownMode::Immediate
+    }
+}
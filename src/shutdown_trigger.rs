@@ -0,0 +1,68 @@
+use std::iter::FromIterator;
+
+use futures::{Async, Future, Poll};
+use futures::sync::oneshot;
+
+pub struct ShutdownTrigger {
+    senders: Vec<oneshot::Sender<()>>,
+}
+
+impl ShutdownTrigger {
+    pub fn new() -> (Self, ShutdownSignal) {
+        let (sender, receiver) = oneshot::channel();
+
+        let trigger = ShutdownTrigger {
+            senders: vec![sender],
+        };
+        let signal = ShutdownSignal {
+            receiver: Some(receiver),
+        };
+
+        (trigger, signal)
+    }
+
+    pub fn shutdown(self) {
+        for sender in self.senders {
+            let _ = sender.send(());
+        }
+    }
+}
+
+impl FromIterator<ShutdownTrigger> for ShutdownTrigger {
+    fn from_iter<I>(triggers: I) -> Self
+    where
+        I: IntoIterator<Item = ShutdownTrigger>,
+    {
+        let senders = triggers
+            .into_iter()
+            .flat_map(|trigger| trigger.senders)
+            .collect();
+
+        ShutdownTrigger { senders }
+    }
+}
+
+pub struct ShutdownSignal {
+    receiver: Option<oneshot::Receiver<()>>,
+}
+
+impl Future for ShutdownSignal {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let poll_result = match self.receiver {
+            Some(ref mut receiver) => receiver.poll(),
+            None => return Ok(Async::NotReady),
+        };
+
+        match poll_result {
+            Ok(Async::Ready(())) => Ok(Async::Ready(())),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(_) => {
+                self.receiver = None;
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+
+use futures::future;
+use tokio_core::reactor::Core;
+use tokio_proto::pipeline::ServerProto;
+use tokio_service::NewService;
+
+use super::async_server_builder::AsyncServerBuilder;
+use super::async_server_error::AsyncServerError;
+use super::controlled_server::ControlledServer;
+use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::server_state::ServerState;
+use super::server_thread::ServerThread;
+use super::session_summary::SessionSummary;
+use super::shutdown_trigger::{ShutdownSignal, ShutdownTrigger};
+
+pub fn spawn_server<S, P>(
+    builder: AsyncServerBuilder<S, P>,
+) -> Result<
+    ServerThread<S::Error, P::Error>,
+    AsyncServerError<S::Error, P::Error>,
+>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    S: Send + 'static,
+    P: ServerProto<MeteredStream> + Send + Sync,
+    S::Instance: FiniteService,
+    S::Error: Send,
+    P::Error: Send,
+{
+    let metrics = builder.shared_metrics();
+    let (address_sender, address_receiver) = mpsc::channel();
+    let (shutdown_trigger, shutdown_signal) = ShutdownTrigger::new();
+    let state = Arc::new(Mutex::new(ServerState::default()));
+    let thread_state = state.clone();
+
+    let join_handle = thread::spawn(move || {
+        run_server(builder, shutdown_signal, address_sender, thread_state)
+    });
+
+    match address_receiver.recv() {
+        Ok(local_address) => Ok(ServerThread::new(
+            local_address,
+            shutdown_trigger,
+            join_handle,
+            metrics,
+            state,
+        )),
+        Err(_) => match join_handle.join() {
+            Ok(Err(error)) => Err(error),
+            _ => Err(AsyncServerError::ServerThreadPanicked),
+        },
+    }
+}
+
+fn run_server<S, P>(
+    builder: AsyncServerBuilder<S, P>,
+    shutdown_signal: ShutdownSignal,
+    address_sender: mpsc::Sender<SocketAddr>,
+    state: Arc<Mutex<ServerState>>,
+) -> Result<SessionSummary, AsyncServerError<S::Error, P::Error>>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    let mut core =
+        Core::new().map_err(AsyncServerError::ReactorCreationError)?;
+    let mut server = builder.build(core.handle())?;
+
+    let local_address = core.run(future::poll_fn(|| server.poll_bind()))?;
+
+    let server =
+        ControlledServer::new(server, shutdown_signal).with_shared_state(state);
+
+    let _ = address_sender.send(local_address);
+
+    core.run(server)
+}
@@ -0,0 +1,43 @@
+use tokio_proto::pipeline::ServerProto;
+use tokio_service::NewService;
+
+use super::async_server_builder::AsyncServerBuilder;
+use super::async_server_error::AsyncServerError;
+use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::server_threads::ServerThreads;
+use super::spawn_server::spawn_server;
+
+pub fn spawn_servers<S, P>(
+    builder: AsyncServerBuilder<S, P>,
+    thread_count: usize,
+) -> Result<
+    ServerThreads<S::Error, P::Error>,
+    AsyncServerError<S::Error, P::Error>,
+>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    S: Clone + Send + 'static,
+    P: ServerProto<MeteredStream> + Send + Sync,
+    S::Instance: FiniteService,
+    S::Error: Send,
+    P::Error: Send,
+{
+    let builder = builder
+        .share_listener()
+        .map_err(AsyncServerError::BindSocketError)?;
+    let metrics = builder.shared_metrics();
+    let mut threads = Vec::with_capacity(thread_count);
+
+    for _ in 0..thread_count {
+        match spawn_server(builder.clone()) {
+            Ok(thread) => threads.push(thread),
+            Err(error) => {
+                ServerThreads::new(threads, metrics).shutdown();
+                return Err(error);
+            }
+        }
+    }
+
+    Ok(ServerThreads::new(threads, metrics))
+}
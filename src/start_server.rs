@@ -1,82 +1,299 @@
-use std::net::SocketAddr;
-use std::sync::{Arc, Mutex};
+use std::io;
+use std::net::{self, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
 
 use futures::{Async, Future, Poll};
-use tokio_core::net::{TcpListener, TcpStream};
-use tokio_core::reactor::Handle;
+use tokio_core::net::TcpListener;
+use tokio_core::reactor::{Handle, Timeout};
 use tokio_proto::pipeline::ServerProto;
 use tokio_service::NewService;
 
+use super::adapters::SharedInstanceStop;
 use super::async_server_error::AsyncServerError;
+use super::bind_listener::bind_listener;
 use super::finite_service::FiniteService;
 use super::listening_server::ListeningServer;
+use super::metered_stream::MeteredStream;
+use super::rate_limit_policy::RateLimitRejection;
+use super::server_metrics::ServerMetrics;
+use super::server_options::ServerOptions;
+use super::server_observer::ServerObserver;
+use super::session_id::SessionId;
+use super::session_observer::SessionObserver;
 
-pub struct StartServer<S, P> {
-    address: SocketAddr,
+pub struct StartServer<S, P>
+where
+    S: NewService,
+    P: ServerProto<MeteredStream>,
+{
+    addresses: Vec<SocketAddr>,
+    shared_listener: Option<Arc<net::TcpListener>>,
     service_factory: Option<S>,
-    protocol: Arc<Mutex<P>>,
+    protocol: Arc<P>,
     handle: Handle,
+    options: ServerOptions,
+    observer: SessionObserver<S::Error, P::Error>,
+    rate_limit_rejection: Option<RateLimitRejection<S::Request, S::Response>>,
+    shared_instance_stop: Option<SharedInstanceStop<S::Error>>,
+    bind_attempts: usize,
+    first_bind_attempt: Option<Instant>,
+    bind_retry: Option<Timeout>,
 }
 
 impl<S, P> StartServer<S, P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S: NewService<Request = P::Request, Response = P::Response>,
     S::Instance: FiniteService,
 {
-    pub fn new(
+    pub fn new<A>(
         address: SocketAddr,
         service_factory: S,
-        protocol: Arc<Mutex<P>>,
+        protocol: A,
+        handle: Handle,
+    ) -> Self
+    where
+        A: Into<Arc<P>>,
+    {
+        Self::with_options(
+            vec![address],
+            service_factory,
+            protocol.into(),
+            handle,
+            ServerOptions::default(),
+        )
+    }
+
+    pub fn with_options(
+        addresses: Vec<SocketAddr>,
+        service_factory: S,
+        protocol: Arc<P>,
         handle: Handle,
+        options: ServerOptions,
     ) -> Self {
+        let session_id = SessionId::next();
+
+        debug!("session {}: binding to {:?}", session_id, addresses);
+
         Self {
-            address,
+            addresses,
+            shared_listener: None,
             protocol,
             handle,
+            options,
+            observer: SessionObserver::new(session_id, None),
+            rate_limit_rejection: None,
+            shared_instance_stop: None,
             service_factory: Some(service_factory),
+            bind_attempts: 0,
+            first_bind_attempt: None,
+            bind_retry: None,
         }
     }
 
+    pub fn with_shared_listener(
+        mut self,
+        listener: Arc<net::TcpListener>,
+    ) -> Self {
+        self.shared_listener = Some(listener);
+        self
+    }
+
+    pub fn with_observer(
+        mut self,
+        observer: Arc<dyn ServerObserver<S::Error, P::Error>>,
+    ) -> Self {
+        let session_id = self.observer.session_id();
+
+        self.observer = SessionObserver::new(session_id, Some(observer));
+        self
+    }
+
+    pub fn with_rate_limit_rejection(
+        mut self,
+        rejection: RateLimitRejection<S::Request, S::Response>,
+    ) -> Self {
+        self.rate_limit_rejection = Some(rejection);
+        self
+    }
+
+    pub(crate) fn with_shared_instance_stop(
+        mut self,
+        shared_instance_stop: SharedInstanceStop<S::Error>,
+    ) -> Self {
+        self.shared_instance_stop = Some(shared_instance_stop);
+        self
+    }
+
+    pub fn session_id(&self) -> SessionId {
+        self.observer.session_id()
+    }
+
+    pub fn observer(&self) -> &SessionObserver<S::Error, P::Error> {
+        &self.observer
+    }
+
+    pub fn metrics(&self) -> &Arc<ServerMetrics> {
+        &self.options.metrics
+    }
+
     pub fn shutdown(
         &mut self,
     ) -> Poll<(), AsyncServerError<S::Error, P::Error>> {
-        if let Some(service_factory) = self.service_factory.take() {
-            let mut service = service_factory.new_service()
-                .map_err(AsyncServerError::ServiceCreationError)?;
-
-            match service.force_stop() {
-                Ok(()) => Ok(Async::Ready(())),
-                Err(error) => {
-                    Err(AsyncServerError::ServiceShutdownError(error))
-                }
-            }
-        } else {
-            Err(AsyncServerError::IncorrectShutdownInStartServer)
+        match self.service_factory.take() {
+            Some(_) => self.stop_shared_instance(),
+            None => Err(AsyncServerError::IncorrectShutdownInStartServer),
         }
     }
 
+    fn stop_shared_instance(
+        &mut self,
+    ) -> Poll<(), AsyncServerError<S::Error, P::Error>> {
+        if let Some(ref stop) = self.shared_instance_stop {
+            stop().map_err(AsyncServerError::ServiceShutdownError)?;
+        }
+
+        Ok(Async::Ready(()))
+    }
+
     fn start_server(
         &mut self,
     ) -> Poll<ListeningServer<S, P>, AsyncServerError<S::Error, P::Error>> {
-        if let Some(service_factory) = self.service_factory.take() {
-            let listener = TcpListener::bind(&self.address, &self.handle)
-                .map_err(AsyncServerError::BindSocketError)?;
+        if !self.bind_retry_elapsed()? {
+            return Ok(Async::NotReady);
+        }
+
+        if self.service_factory.is_none() {
+            return Err(AsyncServerError::AttemptToStartServerTwice);
+        }
+
+        let listener = match self.bind_listener() {
+            Ok(listener) => listener,
+            Err(error) => {
+                self.retry_bind(error)?;
+
+                return self.start_server();
+            }
+        };
+
+        let service_factory = self.service_factory
+            .take()
+            .ok_or(AsyncServerError::AttemptToStartServerTwice)?;
+        let local_address = listener
+            .local_addr()
+            .map_err(AsyncServerError::BindSocketError)?;
+
+        self.observer.bound(local_address);
+
+        let accept_timeout = match self.options.accept_timeout {
+            Some(duration) => Some(
+                Timeout::new(duration, &self.handle)
+                    .map_err(AsyncServerError::AcceptTimerError)?,
+            ),
+            None => None,
+        };
+
+        let protocol = self.protocol.clone();
+        let options = self.options.clone();
+
+        let listening_server = ListeningServer::with_options(
+            listener,
+            Ok(local_address),
+            service_factory,
+            protocol,
+            options,
+            accept_timeout,
+            Some(self.handle.clone()),
+            self.observer.clone(),
+        );
+
+        let listening_server = match self.shared_instance_stop.clone() {
+            Some(stop) => listening_server.with_shared_instance_stop(stop),
+            None => listening_server,
+        };
+
+        Ok(Async::Ready(match self.rate_limit_rejection.clone() {
+            Some(rejection) => {
+                listening_server.with_rate_limit_rejection(rejection)
+            }
+            None => listening_server,
+        }))
+    }
 
-            let protocol = self.protocol.clone();
+    fn bind_retry_elapsed(
+        &mut self,
+    ) -> Result<bool, AsyncServerError<S::Error, P::Error>> {
+        let elapsed = match self.bind_retry {
+            Some(ref mut bind_retry) => bind_retry
+                .poll()
+                .map_err(AsyncServerError::BindRetryTimerError)?
+                .is_ready(),
+            None => true,
+        };
 
-            Ok(Async::Ready(
-                ListeningServer::new(listener, service_factory, protocol),
-            ))
-        } else {
-            Err(AsyncServerError::AttemptToStartServerTwice)
+        if elapsed {
+            self.bind_retry = None;
         }
+
+        Ok(elapsed)
+    }
+
+    fn retry_bind(
+        &mut self,
+        error: io::Error,
+    ) -> Result<(), AsyncServerError<S::Error, P::Error>> {
+        let policy = match self.options.bind_retry {
+            Some(policy) if error.kind() == io::ErrorKind::AddrInUse => policy,
+            _ => return Err(AsyncServerError::BindSocketError(error)),
+        };
+
+        let first_bind_attempt =
+            *self.first_bind_attempt.get_or_insert_with(Instant::now);
+
+        self.bind_attempts += 1;
+
+        let attempts_exhausted = self.bind_attempts >= policy.max_attempts;
+        let deadline_reached = match policy.deadline {
+            Some(deadline) => {
+                first_bind_attempt.elapsed() + policy.backoff > deadline
+            }
+            None => false,
+        };
+
+        if attempts_exhausted || deadline_reached {
+            return Err(AsyncServerError::BindSocketError(error));
+        }
+
+        warn!(
+            "session {}: address in use, retrying bind in {:?} (attempt {})",
+            self.session_id(),
+            policy.backoff,
+            self.bind_attempts
+        );
+
+        let bind_retry = Timeout::new(policy.backoff, &self.handle)
+            .map_err(AsyncServerError::BindRetryTimerError)?;
+
+        self.bind_retry = Some(bind_retry);
+
+        Ok(())
+    }
+
+    fn bind_listener(&self) -> io::Result<TcpListener> {
+        let listener = match self.shared_listener {
+            Some(ref listener) => listener.try_clone()?,
+            None => bind_listener(&self.addresses, &self.options)?,
+        };
+        let local_address = listener.local_addr()?;
+
+        TcpListener::from_listener(listener, &local_address, &self.handle)
     }
 }
 
 impl<S, P> Future for StartServer<S, P>
 where
-    P: ServerProto<TcpStream>,
+    P: ServerProto<MeteredStream>,
     S: NewService<Request = P::Request, Response = P::Response>,
     S::Instance: FiniteService,
 {
@@ -87,3 +304,112 @@ where
         self.start_server()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::{SocketAddr, TcpListener};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use futures::{future, Future};
+    use tokio_core::reactor::{Core, Handle};
+
+    use super::super::async_server_error::AsyncServerError;
+    use super::super::bind_retry_policy::BindRetryPolicy;
+    use super::super::server_options::ServerOptions;
+    use super::super::testing::{MockServiceFactory, ScriptedProto};
+    use super::StartServer;
+
+    fn start_server(
+        address: SocketAddr,
+        bind_retry: Option<BindRetryPolicy>,
+        handle: Handle,
+    ) -> StartServer<MockServiceFactory, ScriptedProto> {
+        let options = ServerOptions {
+            bind_retry,
+            ..ServerOptions::default()
+        };
+
+        StartServer::with_options(
+            vec![address],
+            MockServiceFactory,
+            Arc::new(ScriptedProto),
+            handle,
+            options,
+        )
+    }
+
+    fn failed_as_address_in_use<T, S, P>(
+        result: Result<T, AsyncServerError<S, P>>,
+    ) -> bool {
+        match result {
+            Err(AsyncServerError::BindSocketError(error)) => {
+                error.kind() == io::ErrorKind::AddrInUse
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn busy_address_fails_at_once_without_a_policy() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let mut server = start_server(address, None, core.handle());
+
+        let result = core.run(future::lazy(|| server.poll()));
+
+        assert!(failed_as_address_in_use(result));
+    }
+
+    #[test]
+    fn bind_is_retried_until_the_address_is_free() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let policy = BindRetryPolicy::new(100, Duration::from_millis(10));
+        let server = start_server(address, Some(policy), core.handle());
+
+        let release = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(taken);
+        });
+
+        let listening_server = core.run(server).unwrap();
+
+        release.join().unwrap();
+
+        assert_eq!(listening_server.local_addr(), Some(address));
+    }
+
+    #[test]
+    fn bind_retries_stop_after_the_last_attempt() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let policy = BindRetryPolicy::new(3, Duration::from_millis(1));
+        let mut server = start_server(address, Some(policy), core.handle());
+
+        let result = core.run(&mut server);
+
+        assert!(failed_as_address_in_use(result));
+        assert_eq!(server.bind_attempts, 3);
+    }
+
+    #[test]
+    fn bind_retries_stop_at_the_deadline() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let policy = BindRetryPolicy::new(100, Duration::from_millis(10))
+            .deadline(Duration::from_millis(25));
+        let mut server = start_server(address, Some(policy), core.handle());
+
+        let result = core.run(&mut server);
+
+        assert!(failed_as_address_in_use(result));
+        assert!(server.bind_attempts < 100);
+    }
+}
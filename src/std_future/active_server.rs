@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures03::stream::FuturesUnordered;
+use futures03::{Sink, Stream, StreamExt};
+
+use super::finite_service::FiniteService;
+use super::super::async_server_error::AsyncServerError;
+use super::super::shutdown_mode::ShutdownMode;
+use super::super::status::Status;
+
+pub struct ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Sink<S::Response>,
+{
+    connection: T,
+    service: S,
+    live_requests: FuturesUnordered<Pin<Box<S::Future>>>,
+    live_responses: VecDeque<S::Response>,
+    status: Status<AsyncServerError<S::Error, <T as Sink<S::Response>>::Error>>,
+    shutdown_mode: ShutdownMode,
+    connection_closed: bool,
+    unflushed_responses: bool,
+    stop_requested: bool,
+}
+
+impl<S, T, E> ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Sink<S::Response, Error = E>
+        + Stream<Item = Result<S::Request, E>>
+        + Unpin,
+{
+    pub fn new(connection: T, service: S) -> Self {
+        Self {
+            connection,
+            service,
+            live_requests: FuturesUnordered::new(),
+            live_responses: VecDeque::new(),
+            status: Status::Active,
+            shutdown_mode: ShutdownMode::default(),
+            connection_closed: false,
+            unflushed_responses: false,
+            stop_requested: false,
+        }
+    }
+
+    pub fn with_shutdown_mode(mut self, shutdown_mode: ShutdownMode) -> Self {
+        self.shutdown_mode = shutdown_mode;
+        self
+    }
+
+    pub fn poll_shutdown(
+        &mut self,
+        context: &mut Context,
+    ) -> Poll<Result<(), AsyncServerError<S::Error, E>>> {
+        if !self.stop_requested {
+            self.stop_requested = true;
+
+            if let Err(error) = self.service.request_stop() {
+                let error = AsyncServerError::ServiceShutdownError(error);
+
+                return Poll::Ready(Err(error));
+            }
+        }
+
+        if self.shutdown_mode == ShutdownMode::Graceful {
+            match self.drain(context) {
+                Poll::Ready(Ok(())) => {}
+                drain_poll => return drain_poll,
+            }
+        }
+
+        self.service
+            .poll_stop(context)
+            .map_err(AsyncServerError::ServiceShutdownError)
+    }
+
+    fn drain(
+        &mut self,
+        context: &mut Context,
+    ) -> Poll<Result<(), AsyncServerError<S::Error, E>>> {
+        while self.status.is_active() {
+            self.try_to_get_new_responses(context)
+                .try_to_send_responses(context)
+                .try_to_flush_responses(context)
+                .check_if_drained();
+        }
+
+        self.poll_status()
+    }
+
+    fn service_is_ready(&mut self, context: &mut Context) -> bool {
+        match self.service.poll_ready(context) {
+            Poll::Ready(Ok(())) => true,
+            Poll::Ready(Err(error)) => {
+                self.status.update(Status::Error(
+                    AsyncServerError::ServiceReadinessError(error),
+                ));
+                false
+            }
+            Poll::Pending => {
+                self.status.update(Status::WouldBlock);
+                false
+            }
+        }
+    }
+
+    fn try_to_get_new_request(&mut self, context: &mut Context) -> &mut Self {
+        let can_receive = self.status.is_running()
+            && !self.connection_closed
+            && !self.stop_requested
+            && self.service_is_ready(context);
+
+        if can_receive {
+            match Pin::new(&mut self.connection).poll_next(context) {
+                Poll::Ready(Some(Ok(request))) => {
+                    let response = self.service.call(request);
+
+                    self.live_requests.push(Box::pin(response));
+                }
+                Poll::Ready(Some(Err(error))) => {
+                    self.status.update(Status::Error(
+                        AsyncServerError::NewRequestError(error),
+                    ));
+                }
+                Poll::Ready(None) => self.connection_closed = true,
+                Poll::Pending => self.status.update(Status::WouldBlock),
+            }
+        }
+
+        self
+    }
+
+    fn try_to_get_new_responses(&mut self, context: &mut Context) -> &mut Self {
+        while self.status.is_running() {
+            match self.live_requests.poll_next_unpin(context) {
+                Poll::Ready(Some(Ok(response))) => {
+                    self.live_responses.push_back(response);
+                }
+                Poll::Ready(Some(Err(error))) => {
+                    self.status.update(Status::Error(
+                        AsyncServerError::NewResponseError(error),
+                    ));
+                }
+                Poll::Ready(None) => break,
+                Poll::Pending => {
+                    self.status.update(Status::WouldBlock);
+                    break;
+                }
+            }
+        }
+
+        self
+    }
+
+    fn try_to_send_responses(&mut self, context: &mut Context) -> &mut Self {
+        while self.status.is_running() && !self.live_responses.is_empty() {
+            match Pin::new(&mut self.connection).poll_ready(context) {
+                Poll::Ready(Ok(())) => {
+                    let response = self.live_responses.pop_front().expect(
+                        "live responses queue was checked to not be empty",
+                    );
+
+                    match Pin::new(&mut self.connection).start_send(response) {
+                        Ok(()) => self.unflushed_responses = true,
+                        Err(error) => {
+                            self.status.update(Status::Error(
+                                AsyncServerError::SendResponseError(error),
+                            ));
+                        }
+                    }
+                }
+                Poll::Ready(Err(error)) => {
+                    self.status.update(Status::Error(
+                        AsyncServerError::SendResponseError(error),
+                    ));
+                }
+                Poll::Pending => {
+                    self.status.update(Status::WouldBlock);
+                    break;
+                }
+            }
+        }
+
+        self
+    }
+
+    fn try_to_flush_responses(&mut self, context: &mut Context) -> &mut Self {
+        if self.status.is_running() {
+            match Pin::new(&mut self.connection).poll_flush(context) {
+                Poll::Ready(Ok(())) => self.unflushed_responses = false,
+                Poll::Ready(Err(error)) => {
+                    self.status.update(Status::Error(
+                        AsyncServerError::FlushResponsesError(error),
+                    ));
+                }
+                Poll::Pending => self.status.update(Status::WouldBlock),
+            }
+        }
+
+        self
+    }
+
+    fn check_if_finished(&mut self, context: &mut Context) {
+        if self.status.is_running() {
+            let no_pending_requests = self.live_requests.is_empty();
+            let no_pending_responses = self.live_responses.is_empty();
+
+            if no_pending_requests && no_pending_responses {
+                let service_status = match self.service.poll_finished(context)
+                {
+                    Poll::Ready(Ok(())) if !self.unflushed_responses => {
+                        // Nothing is left to send or flush, so the finish
+                        // must not be hidden by a pending read.
+                        self.status = Status::Finished;
+                        return;
+                    }
+                    Poll::Ready(Ok(())) => Status::Finished,
+                    Poll::Pending if self.connection_closed => {
+                        Status::Finished
+                    }
+                    Poll::Pending => Status::Active,
+                    Poll::Ready(Err(error)) => Status::Error(
+                        AsyncServerError::ServiceFinishedCheckError(error),
+                    ),
+                };
+
+                self.status.update(service_status);
+            }
+        }
+    }
+
+    fn check_if_drained(&mut self) {
+        if self.status.is_active() {
+            let no_pending_requests = self.live_requests.is_empty();
+            let no_pending_responses = self.live_responses.is_empty();
+
+            if no_pending_requests && no_pending_responses {
+                self.status.update(Status::Finished);
+            }
+        }
+    }
+
+    fn poll_status(
+        &mut self,
+    ) -> Poll<Result<(), AsyncServerError<S::Error, E>>> {
+        match mem::replace(&mut self.status, Status::Active) {
+            Status::Finished => Poll::Ready(Ok(())),
+            Status::WouldBlock => Poll::Pending,
+            Status::Error(error) => Poll::Ready(Err(error)),
+            Status::Active => {
+                unreachable!("ActiveServer shouldn't park while it is active");
+            }
+        }
+    }
+}
+
+impl<S, T> Unpin for ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Sink<S::Response>,
+{
+}
+
+impl<S, T, E> Future for ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Sink<S::Response, Error = E>
+        + Stream<Item = Result<S::Request, E>>
+        + Unpin,
+{
+    type Output = Result<(), AsyncServerError<S::Error, E>>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while this.status.is_active() {
+            this.try_to_get_new_request(context)
+                .try_to_get_new_responses(context)
+                .try_to_send_responses(context)
+                .try_to_flush_responses(context)
+                .check_if_finished(context);
+        }
+
+        this.poll_status()
+    }
+}
@@ -0,0 +1,267 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use super::active_server::ActiveServer;
+use super::listening_server::ListeningServer;
+use super::new_service::NewService;
+use super::server_protocol::ServerProtocol;
+use super::start_server::StartServer;
+use super::super::async_server_error::AsyncServerError;
+use super::super::server_phase::ServerPhase;
+use super::super::shutdown_mode::ShutdownMode;
+
+pub enum AsyncServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProtocol,
+{
+    Binding(StartServer<S, P>),
+    Listening(ListeningServer<S, P>),
+    Active(ActiveServer<S::Instance, P::Transport>),
+    Dead,
+}
+
+impl<S, P> AsyncServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProtocol,
+{
+    pub fn new<A>(address: SocketAddr, service_factory: S, protocol: A) -> Self
+    where
+        A: Into<Arc<P>>,
+    {
+        AsyncServer::Binding(StartServer::new(
+            address,
+            service_factory,
+            protocol,
+        ))
+    }
+
+    pub fn with_shutdown_mode(self, shutdown_mode: ShutdownMode) -> Self {
+        match self {
+            AsyncServer::Binding(handler) => {
+                AsyncServer::Binding(handler.with_shutdown_mode(shutdown_mode))
+            }
+            AsyncServer::Listening(handler) => AsyncServer::Listening(
+                handler.with_shutdown_mode(shutdown_mode),
+            ),
+            AsyncServer::Active(handler) => {
+                AsyncServer::Active(handler.with_shutdown_mode(shutdown_mode))
+            }
+            AsyncServer::Dead => AsyncServer::Dead,
+        }
+    }
+
+    pub fn phase(&self) -> ServerPhase {
+        match *self {
+            AsyncServer::Binding(_) => ServerPhase::Binding,
+            AsyncServer::Listening(_) => ServerPhase::Listening,
+            AsyncServer::Active(_) => ServerPhase::Active,
+            AsyncServer::Dead => ServerPhase::Finished,
+        }
+    }
+
+    pub fn poll_shutdown(
+        &mut self,
+        context: &mut Context,
+    ) -> Poll<Result<(), AsyncServerError<S::Error, P::Error>>> {
+        let shutdown_poll = match *self {
+            AsyncServer::Binding(ref mut handler) => {
+                Poll::Ready(handler.shutdown())
+            }
+            AsyncServer::Listening(ref mut handler) => {
+                Poll::Ready(handler.shutdown())
+            }
+            AsyncServer::Active(ref mut handler) => {
+                handler.poll_shutdown(context)
+            }
+            AsyncServer::Dead => Poll::Ready(Ok(())),
+        };
+
+        if shutdown_poll.is_ready() {
+            *self = AsyncServer::Dead;
+        }
+
+        shutdown_poll
+    }
+}
+
+impl<S, P> Unpin for AsyncServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProtocol,
+{
+}
+
+impl<S, P> Future for AsyncServer<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProtocol,
+{
+    type Output = Result<(), AsyncServerError<S::Error, P::Error>>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let poll_result = match *this {
+                AsyncServer::Binding(ref mut handler) => Pin::new(handler)
+                    .poll(context)
+                    .map(|result| result.map(AsyncServer::Listening)),
+                AsyncServer::Listening(ref mut handler) => Pin::new(handler)
+                    .poll(context)
+                    .map(|result| result.map(AsyncServer::Active)),
+                AsyncServer::Active(ref mut handler) => {
+                    return Pin::new(handler).poll(context);
+                }
+                AsyncServer::Dead => {
+                    let error = AsyncServerError::ServerWasShutDown;
+
+                    return Poll::Ready(Err(error));
+                }
+            };
+
+            match poll_result {
+                Poll::Ready(Ok(new_state)) => {
+                    *this = new_state;
+                }
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::future::{self, Ready};
+    use std::io::{self, BufRead, BufReader, Write};
+    use std::net;
+    use std::rc::Rc;
+    use std::thread;
+
+    use futures03::future::poll_fn;
+    use tokio::net::TcpStream;
+    use tokio::runtime::{Builder, Runtime};
+    use tokio_util::codec::{Framed, LinesCodec, LinesCodecError};
+
+    use super::AsyncServer;
+    use super::super::{FiniteService, NewService, ServerProtocol, StartServer};
+
+    struct LineProtocol;
+
+    impl ServerProtocol for LineProtocol {
+        type Request = String;
+        type Response = String;
+        type Error = LinesCodecError;
+        type Transport = Framed<TcpStream, LinesCodec>;
+        type BindTransport = Ready<Result<Self::Transport, Self::Error>>;
+
+        fn bind_transport(&self, io: TcpStream) -> Self::BindTransport {
+            future::ready(Ok(Framed::new(io, LinesCodec::new())))
+        }
+    }
+
+    struct Shout {
+        finished: bool,
+    }
+
+    impl FiniteService for Shout {
+        type Request = String;
+        type Response = String;
+        type Error = io::Error;
+        type Future = Ready<Result<String, io::Error>>;
+
+        fn call(&mut self, request: String) -> Self::Future {
+            self.finished = request == "bye";
+
+            future::ready(Ok(request.to_uppercase()))
+        }
+
+        fn has_finished(&self) -> Result<bool, io::Error> {
+            Ok(self.finished)
+        }
+
+        fn force_stop(&mut self) -> Result<(), io::Error> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct ShoutFactory {
+        created: Rc<Cell<usize>>,
+    }
+
+    impl NewService for ShoutFactory {
+        type Request = String;
+        type Response = String;
+        type Error = io::Error;
+        type Instance = Shout;
+
+        fn new_service(&self) -> io::Result<Shout> {
+            self.created.set(self.created.get() + 1);
+
+            Ok(Shout { finished: false })
+        }
+    }
+
+    fn runtime() -> Runtime {
+        Builder::new_current_thread().enable_io().build().unwrap()
+    }
+
+    fn start_server(
+        factory: ShoutFactory,
+    ) -> StartServer<ShoutFactory, LineProtocol> {
+        let address = "127.0.0.1:0".parse().unwrap();
+
+        StartServer::new(address, factory, LineProtocol)
+    }
+
+    #[test]
+    fn session_runs_until_service_finishes() {
+        let runtime = runtime();
+        let factory = ShoutFactory::default();
+        let listening_server =
+            runtime.block_on(start_server(factory.clone())).unwrap();
+        let address = listening_server.local_addr().unwrap();
+
+        assert_eq!(factory.created.get(), 0);
+
+        let client = thread::spawn(move || {
+            let mut connection = net::TcpStream::connect(address).unwrap();
+
+            connection.write_all(b"hi\nbye\n").unwrap();
+
+            BufReader::new(connection)
+                .lines()
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap()
+        });
+
+        let result = runtime.block_on(AsyncServer::Listening(listening_server));
+
+        assert!(result.is_ok());
+        assert_eq!(client.join().unwrap(), vec!["HI", "BYE"]);
+        assert_eq!(factory.created.get(), 1);
+    }
+
+    #[test]
+    fn shutdown_before_connection_creates_no_service() {
+        let runtime = runtime();
+        let factory = ShoutFactory::default();
+        let listening_server =
+            runtime.block_on(start_server(factory.clone())).unwrap();
+        let mut server = AsyncServer::Listening(listening_server);
+
+        runtime
+            .block_on(poll_fn(|context| server.poll_shutdown(context)))
+            .unwrap();
+
+        assert_eq!(factory.created.get(), 0);
+        assert!(runtime.block_on(server).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use std::future::Future;
+use std::task::{Context, Poll};
+
+pub trait FiniteService {
+    type Request;
+    type Response;
+    type Error;
+    type Future: Future<Output = Result<Self::Response, Self::Error>>;
+
+    fn call(&mut self, request: Self::Request) -> Self::Future;
+    fn has_finished(&self) -> Result<bool, Self::Error>;
+    fn force_stop(&mut self) -> Result<(), Self::Error>;
+
+    fn poll_ready(
+        &mut self,
+        _context: &mut Context,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_finished(
+        &mut self,
+        _context: &mut Context,
+    ) -> Poll<Result<(), Self::Error>> {
+        match self.has_finished() {
+            Ok(true) => Poll::Ready(Ok(())),
+            Ok(false) => Poll::Pending,
+            Err(error) => Poll::Ready(Err(error)),
+        }
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn poll_stop(
+        &mut self,
+        _context: &mut Context,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(self.force_stop())
+    }
+}
@@ -0,0 +1,146 @@
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::net::TcpListener;
+
+use super::active_server::ActiveServer;
+use super::new_service::NewService;
+use super::server_protocol::ServerProtocol;
+use super::super::async_server_error::AsyncServerError;
+use super::super::bound_connection_future::BindConnectionError;
+use super::super::connection_error::ConnectionError;
+use super::super::shutdown_mode::ShutdownMode;
+
+pub struct ListeningServer<S, P>
+where
+    P: ServerProtocol,
+    S: NewService,
+{
+    listener: TcpListener,
+    protocol: Arc<P>,
+    bind_result: Option<Pin<Box<P::BindTransport>>>,
+    service_factory: Option<S>,
+    shutdown_mode: ShutdownMode,
+}
+
+impl<S, P> ListeningServer<S, P>
+where
+    P: ServerProtocol,
+    S: NewService<Request = P::Request, Response = P::Response>,
+{
+    pub fn new(
+        listener: TcpListener,
+        service_factory: S,
+        protocol: Arc<P>,
+    ) -> Self {
+        ListeningServer {
+            listener,
+            protocol,
+            bind_result: None,
+            service_factory: Some(service_factory),
+            shutdown_mode: ShutdownMode::default(),
+        }
+    }
+
+    pub fn with_shutdown_mode(mut self, shutdown_mode: ShutdownMode) -> Self {
+        self.shutdown_mode = shutdown_mode;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn shutdown(
+        &mut self,
+    ) -> Result<(), AsyncServerError<S::Error, P::Error>> {
+        match self.service_factory.take() {
+            Some(_) => Ok(()),
+            None => Err(AsyncServerError::IncorrectShutdownInListeningServer),
+        }
+    }
+
+    fn service(
+        &mut self,
+    ) -> Result<S::Instance, AsyncServerError<S::Error, P::Error>> {
+        let service_factory = self
+            .service_factory
+            .take()
+            .ok_or(AsyncServerError::ListenedTwice)?;
+
+        service_factory
+            .new_service()
+            .map_err(AsyncServerError::ServiceCreationError)
+    }
+}
+
+impl<S, P> Unpin for ListeningServer<S, P>
+where
+    P: ServerProtocol,
+    S: NewService,
+{
+}
+
+impl<S, P> Future for ListeningServer<S, P>
+where
+    P: ServerProtocol,
+    S: NewService<Request = P::Request, Response = P::Response>,
+{
+    type Output = Result<
+        ActiveServer<S::Instance, P::Transport>,
+        AsyncServerError<S::Error, P::Error>,
+    >;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        context: &mut Context,
+    ) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.bind_result.is_none() {
+            match this.listener.poll_accept(context) {
+                Poll::Ready(Ok((socket, _))) => {
+                    let bind_result = this.protocol.bind_transport(socket);
+
+                    this.bind_result = Some(Box::pin(bind_result));
+                }
+                Poll::Ready(Err(cause)) => {
+                    let error = BindConnectionError::NoConnectionToBind(
+                        ConnectionError::FailedToReceiveConnection(cause),
+                    );
+
+                    return Poll::Ready(Err(AsyncServerError::BindError(error)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let bind_poll = match this.bind_result {
+            Some(ref mut bind_result) => bind_result.as_mut().poll(context),
+            None => unreachable!("connection is bound after it is accepted"),
+        };
+
+        let connection = match bind_poll {
+            Poll::Ready(Ok(connection)) => connection,
+            Poll::Ready(Err(cause)) => {
+                let error = BindConnectionError::BindError(cause);
+
+                return Poll::Ready(Err(AsyncServerError::BindError(error)));
+            }
+            Poll::Pending => return Poll::Pending,
+        };
+
+        this.bind_result = None;
+
+        let shutdown_mode = this.shutdown_mode;
+
+        Poll::Ready(this.service().map(|service| {
+            ActiveServer::new(connection, service)
+                .with_shutdown_mode(shutdown_mode)
+        }))
+    }
+}
@@ -0,0 +1,14 @@
+mod active_server;
+mod async_server;
+mod finite_service;
+mod listening_server;
+mod new_service;
+mod server_protocol;
+mod start_server;
+
+pub use self::async_server::AsyncServer;
+pub use self::finite_service::FiniteService;
+pub use self::listening_server::ListeningServer;
+pub use self::new_service::NewService;
+pub use self::server_protocol::ServerProtocol;
+pub use self::start_server::StartServer;
@@ -0,0 +1,16 @@
+use std::io;
+
+use super::finite_service::FiniteService;
+
+pub trait NewService {
+    type Request;
+    type Response;
+    type Error;
+    type Instance: FiniteService<
+        Request = Self::Request,
+        Response = Self::Response,
+        Error = Self::Error,
+    >;
+
+    fn new_service(&self) -> io::Result<Self::Instance>;
+}
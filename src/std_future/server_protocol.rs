@@ -0,0 +1,16 @@
+use std::future::Future;
+
+use futures03::{Sink, Stream};
+use tokio::net::TcpStream;
+
+pub trait ServerProtocol {
+    type Request;
+    type Response;
+    type Error;
+    type Transport: Stream<Item = Result<Self::Request, Self::Error>>
+        + Sink<Self::Response, Error = Self::Error>
+        + Unpin;
+    type BindTransport: Future<Output = Result<Self::Transport, Self::Error>>;
+
+    fn bind_transport(&self, io: TcpStream) -> Self::BindTransport;
+}
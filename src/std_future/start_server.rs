@@ -0,0 +1,93 @@
+use std::future::Future;
+use std::net::{self, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::net::TcpListener;
+
+use super::listening_server::ListeningServer;
+use super::new_service::NewService;
+use super::server_protocol::ServerProtocol;
+use super::super::async_server_error::AsyncServerError;
+use super::super::shutdown_mode::ShutdownMode;
+
+pub struct StartServer<S, P> {
+    address: SocketAddr,
+    service_factory: Option<S>,
+    protocol: Arc<P>,
+    shutdown_mode: ShutdownMode,
+}
+
+impl<S, P> StartServer<S, P>
+where
+    P: ServerProtocol,
+    S: NewService<Request = P::Request, Response = P::Response>,
+{
+    pub fn new<A>(address: SocketAddr, service_factory: S, protocol: A) -> Self
+    where
+        A: Into<Arc<P>>,
+    {
+        Self {
+            address,
+            protocol: protocol.into(),
+            service_factory: Some(service_factory),
+            shutdown_mode: ShutdownMode::default(),
+        }
+    }
+
+    pub fn with_shutdown_mode(mut self, shutdown_mode: ShutdownMode) -> Self {
+        self.shutdown_mode = shutdown_mode;
+        self
+    }
+
+    pub fn shutdown(
+        &mut self,
+    ) -> Result<(), AsyncServerError<S::Error, P::Error>> {
+        match self.service_factory.take() {
+            Some(_) => Ok(()),
+            None => Err(AsyncServerError::IncorrectShutdownInStartServer),
+        }
+    }
+
+    fn start_server(
+        &mut self,
+    ) -> Result<ListeningServer<S, P>, AsyncServerError<S::Error, P::Error>> {
+        let service_factory = self
+            .service_factory
+            .take()
+            .ok_or(AsyncServerError::AttemptToStartServerTwice)?;
+
+        let listener = net::TcpListener::bind(&self.address)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                TcpListener::from_std(listener)
+            })
+            .map_err(AsyncServerError::BindSocketError)?;
+
+        let listening_server = ListeningServer::new(
+            listener,
+            service_factory,
+            self.protocol.clone(),
+        );
+
+        Ok(listening_server.with_shutdown_mode(self.shutdown_mode))
+    }
+}
+
+impl<S, P> Unpin for StartServer<S, P> {}
+
+impl<S, P> Future for StartServer<S, P>
+where
+    P: ServerProtocol,
+    S: NewService<Request = P::Request, Response = P::Response>,
+{
+    type Output = Result<
+        ListeningServer<S, P>,
+        AsyncServerError<S::Error, P::Error>,
+    >;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+        Poll::Ready(self.get_mut().start_server())
+    }
+}
@@ -0,0 +1,46 @@
+use futures::{Async, Future, Poll};
+use tokio_service::Service;
+
+use super::finite_service::FiniteService;
+
+pub struct StopFuture<S> {
+    service: Option<S>,
+    stop_requested: bool,
+}
+
+impl<S> StopFuture<S>
+where
+    S: FiniteService,
+{
+    pub fn new(service: S) -> Self {
+        StopFuture {
+            service: Some(service),
+            stop_requested: false,
+        }
+    }
+}
+
+impl<S> Future for StopFuture<S>
+where
+    S: FiniteService,
+{
+    type Item = S;
+    type Error = <S as Service>::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        {
+            let service = self.service
+                .as_mut()
+                .expect("StopFuture polled after it finished");
+
+            if !self.stop_requested {
+                self.stop_requested = true;
+                service.request_stop()?;
+            }
+
+            try_ready!(service.poll_stop());
+        }
+
+        Ok(Async::Ready(self.service.take().unwrap()))
+    }
+}
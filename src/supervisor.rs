@@ -0,0 +1,325 @@
+use futures::{Async, Future, Poll};
+use futures::sync::mpsc;
+use tokio_core::reactor::{Handle, Timeout};
+use tokio_proto::pipeline::ServerProto;
+use tokio_service::NewService;
+
+use super::async_server::AsyncServer;
+use super::async_server_builder::AsyncServerBuilder;
+use super::async_server_error::AsyncServerError;
+use super::finite_service::FiniteService;
+use super::metered_stream::MeteredStream;
+use super::restart_event::{RestartEvent, RestartEvents};
+use super::restart_policy::RestartPolicy;
+use super::session_summary::SessionSummary;
+
+type Error<S, P> = AsyncServerError<
+    <S as NewService>::Error,
+    <P as ServerProto<MeteredStream>>::Error,
+>;
+
+type RestartPredicate<S, P> =
+    Box<dyn Fn(&Error<S, P>) -> bool + Send + Sync>;
+
+enum SupervisorState<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    Starting,
+    Running(AsyncServer<S, P>),
+    WaitingToRestart(Timeout),
+}
+
+pub struct Supervisor<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response>,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    builder: AsyncServerBuilder<S, P>,
+    handle: Handle,
+    policy: RestartPolicy,
+    restartable: Option<RestartPredicate<S, P>>,
+    state: SupervisorState<S, P>,
+    restarts: usize,
+    shutting_down: bool,
+    events: mpsc::UnboundedSender<RestartEvent>,
+}
+
+impl<S, P> Supervisor<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response> + Clone,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    pub fn new(
+        builder: AsyncServerBuilder<S, P>,
+        handle: Handle,
+        policy: RestartPolicy,
+    ) -> (Self, RestartEvents) {
+        let (events, receiver) = mpsc::unbounded();
+
+        let supervisor = Supervisor {
+            builder,
+            handle,
+            policy,
+            restartable: None,
+            state: SupervisorState::Starting,
+            restarts: 0,
+            shutting_down: false,
+            events,
+        };
+
+        (supervisor, receiver)
+    }
+
+    pub fn restart_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Error<S, P>) -> bool + Send + Sync + 'static,
+    {
+        self.restartable = Some(Box::new(predicate));
+        self
+    }
+
+    pub fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    pub fn shutdown(&mut self) -> Poll<SessionSummary, Error<S, P>> {
+        self.shutting_down = true;
+
+        if let SupervisorState::Running(ref mut server) = self.state {
+            return server.shutdown();
+        }
+
+        // Between servers there is nothing to stop, but a server built and
+        // shut down at once still reports the session like any other.
+        let mut server = self.builder.clone().build(self.handle.clone())?;
+        let shutdown_result = server.shutdown();
+
+        self.state = SupervisorState::Running(server);
+
+        shutdown_result
+    }
+
+    fn restart_after(
+        &mut self,
+        error: Error<S, P>,
+    ) -> Result<SupervisorState<S, P>, Error<S, P>> {
+        let restartable = match self.restartable {
+            Some(ref predicate) => predicate(&error),
+            None => error.is_restartable(),
+        };
+
+        if self.shutting_down || !restartable {
+            return Err(error);
+        }
+
+        if let Some(max_restarts) = self.policy.max_restarts {
+            if self.restarts >= max_restarts {
+                error!(
+                    "server failed with {} after {} restarts, giving up",
+                    error.name(),
+                    self.restarts
+                );
+
+                return Err(error);
+            }
+        }
+
+        self.restarts += 1;
+
+        let backoff = self.policy.backoff_for(self.restarts);
+
+        warn!(
+            "server failed with {}, restarting in {:?} (restart {})",
+            error.name(),
+            backoff,
+            self.restarts
+        );
+
+        let _ = self.events.unbounded_send(RestartEvent {
+            restart: self.restarts,
+            error: error.name(),
+            backoff,
+        });
+
+        let timer = Timeout::new(backoff, &self.handle)
+            .map_err(AsyncServerError::RestartTimerError)?;
+
+        Ok(SupervisorState::WaitingToRestart(timer))
+    }
+}
+
+impl<S, P> Future for Supervisor<S, P>
+where
+    S: NewService<Request = P::Request, Response = P::Response> + Clone,
+    P: ServerProto<MeteredStream>,
+    S::Instance: FiniteService,
+{
+    type Item = SessionSummary;
+    type Error = Error<S, P>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let result = match self.state {
+                SupervisorState::Starting => {
+                    self.builder.clone().build(self.handle.clone())
+                }
+                SupervisorState::Running(ref mut server) => {
+                    match server.poll() {
+                        Ok(poll_result) => return Ok(poll_result),
+                        Err(error) => Err(error),
+                    }
+                }
+                SupervisorState::WaitingToRestart(ref mut timer) => {
+                    let poll_result = timer
+                        .poll()
+                        .map_err(AsyncServerError::RestartTimerError)?;
+
+                    if let Async::NotReady = poll_result {
+                        return Ok(Async::NotReady);
+                    }
+
+                    self.state = SupervisorState::Starting;
+                    continue;
+                }
+            };
+
+            self.state = match result {
+                Ok(server) => SupervisorState::Running(server),
+                Err(error) => self.restart_after(error)?,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{SocketAddr, TcpListener};
+    use std::time::Duration;
+
+    use futures::{future, Async, Future, Stream};
+    use tokio_core::reactor::Core;
+
+    use super::super::async_server_builder::AsyncServerBuilder;
+    use super::super::restart_policy::RestartPolicy;
+    use super::super::termination_reason::TerminationReason;
+    use super::super::testing::{MockServiceFactory, ScriptedProto};
+    use super::Supervisor;
+
+    fn builder(
+        address: SocketAddr,
+    ) -> AsyncServerBuilder<MockServiceFactory, ScriptedProto> {
+        AsyncServerBuilder::new()
+            .address(address)
+            .service_factory(MockServiceFactory)
+            .protocol(ScriptedProto)
+    }
+
+    fn restart_policy(max_restarts: usize) -> RestartPolicy {
+        RestartPolicy::new()
+            .max_restarts(max_restarts)
+            .initial_backoff(Duration::from_millis(1))
+            .max_backoff(Duration::from_millis(10))
+    }
+
+    #[test]
+    fn failed_binds_are_restarted_until_the_limit() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let (mut supervisor, events) = Supervisor::new(
+            builder(address),
+            core.handle(),
+            restart_policy(2),
+        );
+
+        let error = core.run(&mut supervisor).unwrap_err();
+
+        assert!(error.is_restartable());
+        assert_eq!(supervisor.restarts(), 2);
+
+        drop(supervisor);
+
+        let events = events.collect().wait().unwrap();
+        let restarts: Vec<_> =
+            events.iter().map(|event| event.restart).collect();
+        let backoffs: Vec<_> =
+            events.iter().map(|event| event.backoff).collect();
+
+        assert_eq!(restarts, vec![1, 2]);
+        assert_eq!(
+            backoffs,
+            vec![Duration::from_millis(1), Duration::from_millis(2)]
+        );
+    }
+
+    #[test]
+    fn refused_errors_are_not_restarted() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let (supervisor, _events) = Supervisor::new(
+            builder(address),
+            core.handle(),
+            restart_policy(2),
+        );
+        let mut supervisor = supervisor.restart_if(|_| false);
+
+        assert!(core.run(&mut supervisor).is_err());
+        assert_eq!(supervisor.restarts(), 0);
+    }
+
+    #[test]
+    fn shutdown_before_starting_reports_a_requested_shutdown() {
+        let mut core = Core::new().unwrap();
+        let address = SocketAddr::from(([127, 0, 0, 1], 0));
+        let (mut supervisor, _events) = Supervisor::new(
+            builder(address),
+            core.handle(),
+            RestartPolicy::new(),
+        );
+
+        let summary = match core.run(future::lazy(|| supervisor.shutdown())) {
+            Ok(Async::Ready(summary)) => summary,
+            _ => panic!("supervisor did not shut down at once"),
+        };
+
+        assert_eq!(
+            summary.termination_reason,
+            TerminationReason::ShutdownRequested
+        );
+        assert_eq!(core.run(&mut supervisor).unwrap(), summary);
+    }
+
+    #[test]
+    fn shutdown_while_waiting_to_restart_stops_restarting() {
+        let mut core = Core::new().unwrap();
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = taken.local_addr().unwrap();
+        let policy =
+            RestartPolicy::new().initial_backoff(Duration::from_secs(3600));
+        let (mut supervisor, _events) =
+            Supervisor::new(builder(address), core.handle(), policy);
+
+        let first_poll = core.run(future::lazy(|| supervisor.poll()));
+
+        assert_eq!(first_poll.unwrap(), Async::NotReady);
+        assert_eq!(supervisor.restarts(), 1);
+
+        let summary = match core.run(future::lazy(|| supervisor.shutdown())) {
+            Ok(Async::Ready(summary)) => summary,
+            _ => panic!("supervisor did not shut down at once"),
+        };
+
+        assert_eq!(
+            summary.termination_reason,
+            TerminationReason::ShutdownRequested
+        );
+        assert_eq!(core.run(&mut supervisor).unwrap(), summary);
+        assert_eq!(supervisor.restarts(), 1);
+    }
+}
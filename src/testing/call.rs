@@ -0,0 +1,9 @@
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Call<Q, R> {
+    Received(Q),
+    Called(Q),
+    Sent(R),
+    Flushed,
+    RequestStop,
+    ForceStop,
+}
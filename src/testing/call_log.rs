@@ -0,0 +1,114 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use super::call::Call;
+
+pub struct CallLog<Q, R> {
+    calls: Arc<Mutex<Vec<Call<Q, R>>>>,
+}
+
+impl<Q, R> CallLog<Q, R>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    pub fn new() -> Self {
+        CallLog {
+            calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn record(&self, call: Call<Q, R>) {
+        self.calls
+            .lock()
+            .expect("call log lock was poisoned")
+            .push(call);
+    }
+
+    pub fn calls(&self) -> Vec<Call<Q, R>> {
+        self.calls.lock().expect("call log lock was poisoned").clone()
+    }
+
+    pub fn responses(&self) -> Vec<R> {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                Call::Sent(response) => Some(response),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn assert_calls(&self, expected: &[Call<Q, R>]) {
+        let calls = self.calls();
+
+        assert_eq!(calls.as_slice(), expected, "unexpected calls");
+    }
+
+    pub fn assert_order(&self, expected: &[Call<Q, R>]) {
+        let calls = self.calls();
+        let mut remaining_calls = calls.iter();
+
+        for expected_call in expected {
+            let found = remaining_calls.any(|call| call == expected_call);
+
+            assert!(
+                found,
+                "expected {:?} in order {:?}, but calls were {:?}",
+                expected_call, expected, calls
+            );
+        }
+    }
+}
+
+impl<Q, R> Clone for CallLog<Q, R> {
+    fn clone(&self) -> Self {
+        CallLog {
+            calls: self.calls.clone(),
+        }
+    }
+}
+
+impl<Q, R> Default for CallLog<Q, R>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    fn default() -> Self {
+        CallLog::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::call::Call;
+    use super::CallLog;
+
+    fn call_log() -> CallLog<u32, u32> {
+        let call_log = CallLog::new();
+
+        call_log.record(Call::Received(1));
+        call_log.record(Call::Called(1));
+        call_log.record(Call::Sent(10));
+        call_log.record(Call::Flushed);
+
+        call_log
+    }
+
+    #[test]
+    fn order_allows_calls_in_between() {
+        call_log().assert_order(&[Call::Received(1), Call::Flushed]);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected Received(1) in order")]
+    fn order_rejects_calls_out_of_order() {
+        call_log().assert_order(&[Call::Sent(10), Call::Received(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected calls")]
+    fn calls_must_match_exactly() {
+        call_log().assert_calls(&[Call::Received(1), Call::Sent(10)]);
+    }
+}
@@ -0,0 +1,40 @@
+use futures::{Async, Future, Poll};
+use futures::task;
+
+use super::mock_response::MockResponse;
+
+pub struct MockFuture<R, E> {
+    response: Option<MockResponse<R, E>>,
+}
+
+impl<R, E> MockFuture<R, E> {
+    pub fn new(response: MockResponse<R, E>) -> Self {
+        MockFuture {
+            response: Some(response),
+        }
+    }
+}
+
+impl<R, E> Future for MockFuture<R, E> {
+    type Item = R;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let response = self
+            .response
+            .take()
+            .expect("MockFuture polled after it completed");
+
+        match response {
+            MockResponse::Respond(response) => Ok(Async::Ready(response)),
+            MockResponse::Delay(response, 0) => Ok(Async::Ready(response)),
+            MockResponse::Delay(response, polls) => {
+                self.response = Some(MockResponse::Delay(response, polls - 1));
+                task::current().notify();
+
+                Ok(Async::NotReady)
+            }
+            MockResponse::Fail(error) => Err(error),
+        }
+    }
+}
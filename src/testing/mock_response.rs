@@ -0,0 +1,6 @@
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MockResponse<R, E> {
+    Respond(R),
+    Delay(R, usize),
+    Fail(E),
+}
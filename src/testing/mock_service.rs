@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use futures::{Async, Poll};
+use tokio_service::Service;
+
+use super::super::finite_service::FiniteService;
+use super::super::push_channel::PushReceiver;
+use super::call::Call;
+use super::call_log::CallLog;
+use super::mock_future::MockFuture;
+use super::mock_response::MockResponse;
+
+pub struct MockService<Q, R, E> {
+    responses: Mutex<VecDeque<MockResponse<R, E>>>,
+    finish_after: Option<usize>,
+    finish_on: Option<Q>,
+    requests: AtomicUsize,
+    finished: AtomicBool,
+    call_log: Option<CallLog<Q, R>>,
+    push_receiver: Option<PushReceiver<R>>,
+    readiness_error: Option<E>,
+    _error: PhantomData<E>,
+}
+
+impl<Q, R, E> MockService<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    pub fn new() -> Self {
+        MockService {
+            responses: Mutex::new(VecDeque::new()),
+            finish_after: None,
+            finish_on: None,
+            requests: AtomicUsize::new(0),
+            finished: AtomicBool::new(false),
+            call_log: None,
+            push_receiver: None,
+            readiness_error: None,
+            _error: PhantomData,
+        }
+    }
+
+    pub fn respond(self, response: R) -> Self {
+        self.script(MockResponse::Respond(response))
+    }
+
+    pub fn respond_after(self, response: R, polls: usize) -> Self {
+        self.script(MockResponse::Delay(response, polls))
+    }
+
+    pub fn fail(self, error: E) -> Self {
+        self.script(MockResponse::Fail(error))
+    }
+
+    pub fn fail_readiness(mut self, error: E) -> Self {
+        self.readiness_error = Some(error);
+        self
+    }
+
+    pub fn finish_after(mut self, requests: usize) -> Self {
+        self.finish_after = Some(requests);
+        self
+    }
+
+    pub fn finish_on(mut self, request: Q) -> Self {
+        self.finish_on = Some(request);
+        self
+    }
+
+    pub fn with_call_log(mut self, call_log: CallLog<Q, R>) -> Self {
+        self.call_log = Some(call_log);
+        self
+    }
+
+    pub fn with_push_receiver(mut self, receiver: PushReceiver<R>) -> Self {
+        self.push_receiver = Some(receiver);
+        self
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    pub fn remaining_responses(&self) -> usize {
+        self.responses
+            .lock()
+            .expect("mock service responses lock was poisoned")
+            .len()
+    }
+
+    fn script(self, response: MockResponse<R, E>) -> Self {
+        self.responses
+            .lock()
+            .expect("mock service responses lock was poisoned")
+            .push_back(response);
+        self
+    }
+
+    fn record(&self, call: Call<Q, R>) {
+        if let Some(ref call_log) = self.call_log {
+            call_log.record(call);
+        }
+    }
+}
+
+impl<Q, R, E> Default for MockService<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    fn default() -> Self {
+        MockService::new()
+    }
+}
+
+impl<Q, R, E> Service for MockService<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    type Request = Q;
+    type Response = R;
+    type Error = E;
+    type Future = MockFuture<R, E>;
+
+    fn call(&self, request: Self::Request) -> Self::Future {
+        self.record(Call::Called(request.clone()));
+
+        let requests = self.requests.fetch_add(1, Ordering::SeqCst) + 1;
+        let reached_limit = self.finish_after.map_or(false, |limit| {
+            requests >= limit
+        });
+
+        if reached_limit || self.finish_on.as_ref() == Some(&request) {
+            self.finished.store(true, Ordering::SeqCst);
+        }
+
+        let response = self
+            .responses
+            .lock()
+            .expect("mock service responses lock was poisoned")
+            .pop_front()
+            .unwrap_or_else(|| {
+                panic!("MockService has no response scripted for {:?}", request)
+            });
+
+        MockFuture::new(response)
+    }
+}
+
+impl<Q, R, E> FiniteService for MockService<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    fn has_finished(&self) -> Result<bool, Self::Error> {
+        Ok(self.finished.load(Ordering::SeqCst))
+    }
+
+    fn force_stop(&mut self) -> Result<(), Self::Error> {
+        self.record(Call::ForceStop);
+        self.finished.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        match self.readiness_error.take() {
+            Some(error) => Err(error),
+            None => Ok(Async::Ready(())),
+        }
+    }
+
+    fn push_receiver(&mut self) -> Option<PushReceiver<Self::Response>> {
+        self.push_receiver.take()
+    }
+
+    fn request_stop(&mut self) -> Result<(), Self::Error> {
+        self.record(Call::RequestStop);
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use std::io;
+
+use tokio_service::NewService;
+
+use super::mock_service::MockService;
+
+#[derive(Clone)]
+pub struct MockServiceFactory;
+
+impl NewService for MockServiceFactory {
+    type Request = u32;
+    type Response = u32;
+    type Error = io::Error;
+    type Instance = MockService<u32, u32, io::Error>;
+
+    fn new_service(&self) -> io::Result<Self::Instance> {
+        Ok(MockService::new())
+    }
+}
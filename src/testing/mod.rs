@@ -0,0 +1,58 @@
+mod call;
+mod call_log;
+mod mock_future;
+mod mock_response;
+mod mock_service;
+mod mock_service_factory;
+mod scripted_proto;
+mod scripted_transport;
+
+use std::net::SocketAddr;
+
+use futures::{Sink, Stream};
+
+use super::finite_service::FiniteService;
+use super::session_id::SessionId;
+use super::session_observer::SessionObserver;
+
+pub use self::call::Call;
+pub use self::call_log::CallLog;
+pub use self::mock_future::MockFuture;
+pub use self::mock_response::MockResponse;
+pub use self::mock_service::MockService;
+pub use self::mock_service_factory::MockServiceFactory;
+pub use self::scripted_proto::ScriptedProto;
+pub use self::scripted_transport::ScriptedTransport;
+pub use super::active_server::ActiveServer;
+pub use super::server_options::ServerOptions;
+
+pub fn active_server<S, T, E>(service: S, transport: T) -> ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Sink<SinkItem = S::Response, SinkError = E>
+        + Stream<Item = S::Request, Error = E>,
+{
+    active_server_with_options(service, transport, ServerOptions::default())
+}
+
+pub fn active_server_with_options<S, T, E>(
+    service: S,
+    transport: T,
+    options: ServerOptions,
+) -> ActiveServer<S, T>
+where
+    S: FiniteService,
+    T: Sink<SinkItem = S::Response, SinkError = E>
+        + Stream<Item = S::Request, Error = E>,
+{
+    let address = SocketAddr::from(([127, 0, 0, 1], 0));
+
+    ActiveServer::with_options(
+        transport,
+        service,
+        address,
+        address,
+        SessionObserver::new(SessionId::next(), None),
+        options,
+    )
+}
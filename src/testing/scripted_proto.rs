@@ -0,0 +1,21 @@
+use std::io;
+
+use tokio_proto::pipeline::ServerProto;
+
+use super::super::metered_stream::MeteredStream;
+use super::call_log::CallLog;
+use super::scripted_transport::ScriptedTransport;
+
+pub struct ScriptedProto;
+
+impl ServerProto<MeteredStream> for ScriptedProto {
+    type Request = u32;
+    type Response = u32;
+    type Error = io::Error;
+    type Transport = ScriptedTransport<u32, u32, io::Error>;
+    type BindTransport = io::Result<Self::Transport>;
+
+    fn bind_transport(&self, _: MeteredStream) -> Self::BindTransport {
+        Ok(ScriptedTransport::new(CallLog::new()).keep_open())
+    }
+}
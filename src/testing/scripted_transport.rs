@@ -0,0 +1,156 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use futures::{Async, AsyncSink, Poll, Sink, StartSend, Stream};
+use futures::task;
+
+use super::call::Call;
+use super::call_log::CallLog;
+
+enum ScriptStep<Q, E> {
+    Request(Q),
+    Pause,
+    Fail(E),
+}
+
+pub struct ScriptedTransport<Q, R, E> {
+    script: VecDeque<ScriptStep<Q, E>>,
+    call_log: CallLog<Q, R>,
+    send_errors: VecDeque<E>,
+    flush_errors: VecDeque<E>,
+    send_capacity: Option<usize>,
+    unflushed_responses: usize,
+    keep_open: bool,
+}
+
+impl<Q, R, E> ScriptedTransport<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    pub fn new(call_log: CallLog<Q, R>) -> Self {
+        ScriptedTransport {
+            script: VecDeque::new(),
+            call_log,
+            send_errors: VecDeque::new(),
+            flush_errors: VecDeque::new(),
+            send_capacity: None,
+            unflushed_responses: 0,
+            keep_open: false,
+        }
+    }
+
+    pub fn request(mut self, request: Q) -> Self {
+        self.script.push_back(ScriptStep::Request(request));
+        self
+    }
+
+    pub fn pause(mut self) -> Self {
+        self.script.push_back(ScriptStep::Pause);
+        self
+    }
+
+    pub fn fail(mut self, error: E) -> Self {
+        self.script.push_back(ScriptStep::Fail(error));
+        self
+    }
+
+    pub fn fail_send(mut self, error: E) -> Self {
+        self.send_errors.push_back(error);
+        self
+    }
+
+    pub fn fail_flush(mut self, error: E) -> Self {
+        self.flush_errors.push_back(error);
+        self
+    }
+
+    pub fn keep_open(mut self) -> Self {
+        self.keep_open = true;
+        self
+    }
+
+    pub fn send_capacity(mut self, capacity: usize) -> Self {
+        self.send_capacity = Some(capacity);
+        self
+    }
+
+    pub fn call_log(&self) -> &CallLog<Q, R> {
+        &self.call_log
+    }
+}
+
+impl<Q, R, E> Stream for ScriptedTransport<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    type Item = Q;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        match self.script.pop_front() {
+            Some(ScriptStep::Request(request)) => {
+                self.call_log.record(Call::Received(request.clone()));
+
+                Ok(Async::Ready(Some(request)))
+            }
+            Some(ScriptStep::Fail(error)) => Err(error),
+            Some(ScriptStep::Pause) => {
+                task::current().notify();
+
+                Ok(Async::NotReady)
+            }
+            None if self.keep_open => {
+                task::current().notify();
+
+                Ok(Async::NotReady)
+            }
+            None => Ok(Async::Ready(None)),
+        }
+    }
+}
+
+impl<Q, R, E> Sink for ScriptedTransport<Q, R, E>
+where
+    Q: Clone + Debug + PartialEq,
+    R: Clone + Debug + PartialEq,
+{
+    type SinkItem = R;
+    type SinkError = E;
+
+    fn start_send(
+        &mut self,
+        response: Self::SinkItem,
+    ) -> StartSend<Self::SinkItem, Self::SinkError> {
+        if let Some(error) = self.send_errors.pop_front() {
+            return Err(error);
+        }
+
+        if let Some(capacity) = self.send_capacity {
+            if self.unflushed_responses >= capacity {
+                task::current().notify();
+
+                return Ok(AsyncSink::NotReady(response));
+            }
+        }
+
+        self.unflushed_responses += 1;
+        self.call_log.record(Call::Sent(response));
+
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
+        if self.unflushed_responses > 0 {
+            if let Some(error) = self.flush_errors.pop_front() {
+                return Err(error);
+            }
+
+            self.unflushed_responses = 0;
+            self.call_log.record(Call::Flushed);
+        }
+
+        Ok(Async::Ready(()))
+    }
+}
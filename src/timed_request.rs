@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures::{Async, Future, Poll};
+
+use super::server_metrics::ServerMetrics;
+
+pub struct TimedRequest<F> {
+    future: F,
+    started_at: Instant,
+    metrics: Arc<ServerMetrics>,
+}
+
+impl<F> TimedRequest<F>
+where
+    F: Future,
+{
+    pub fn new(future: F, metrics: Arc<ServerMetrics>) -> Self {
+        metrics.request_started();
+
+        TimedRequest {
+            future,
+            metrics,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl<F> Future for TimedRequest<F>
+where
+    F: Future,
+{
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let poll_result = self.future.poll();
+
+        if let Ok(Async::NotReady) = poll_result {
+            return poll_result;
+        }
+
+        self.metrics.request_completed(self.started_at.elapsed());
+
+        poll_result
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    capacity: u32,
+    tokens: u32,
+    refill_interval: Duration,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, refill_interval: Duration) -> Self {
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            refill_interval,
+            last_refill: Instant::now(),
+        }
+    }
+
+    pub fn acquire(&mut self) {
+        self.refill();
+        self.tokens = self.tokens.saturating_sub(1);
+    }
+
+    pub fn time_until_available(&mut self) -> Duration {
+        self.refill();
+
+        if self.tokens > 0 {
+            Duration::from_secs(0)
+        } else {
+            let elapsed = self.last_refill.elapsed();
+
+            if elapsed >= self.refill_interval {
+                Duration::from_secs(0)
+            } else {
+                self.refill_interval - elapsed
+            }
+        }
+    }
+
+    fn refill(&mut self) {
+        let interval = duration_in_nanoseconds(self.refill_interval);
+
+        if interval == 0 {
+            self.tokens = self.capacity;
+            return;
+        }
+
+        let elapsed = duration_in_nanoseconds(self.last_refill.elapsed());
+        let new_tokens = elapsed / interval;
+
+        if new_tokens == 0 {
+            return;
+        }
+
+        let missing_tokens = u64::from(self.capacity - self.tokens);
+
+        if new_tokens >= missing_tokens {
+            self.tokens = self.capacity;
+            self.last_refill = Instant::now();
+        } else {
+            self.tokens += new_tokens as u32;
+            self.last_refill += self.refill_interval * new_tokens as u32;
+        }
+    }
+}
+
+fn duration_in_nanoseconds(duration: Duration) -> u64 {
+    let seconds = duration.as_secs().saturating_mul(1_000_000_000);
+
+    seconds.saturating_add(u64::from(duration.subsec_nanos()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::TokenBucket;
+
+    fn drained_bucket(capacity: u32, refill_interval: Duration) -> TokenBucket {
+        let mut bucket = TokenBucket::new(capacity, refill_interval);
+
+        for _ in 0..capacity {
+            bucket.acquire();
+        }
+
+        bucket
+    }
+
+    #[test]
+    fn full_bucket_allows_a_burst() {
+        let mut bucket = TokenBucket::new(3, Duration::from_secs(60));
+
+        for _ in 0..3 {
+            assert_eq!(bucket.time_until_available(), Duration::from_secs(0));
+            bucket.acquire();
+        }
+
+        let delay = bucket.time_until_available();
+
+        assert!(delay > Duration::from_secs(59));
+        assert!(delay <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn refill_adds_one_token_per_elapsed_interval() {
+        let interval = Duration::from_secs(10);
+        let mut bucket = drained_bucket(5, interval);
+
+        bucket.last_refill = Instant::now() - Duration::from_secs(25);
+        bucket.refill();
+
+        assert_eq!(bucket.tokens, 2);
+    }
+
+    #[test]
+    fn refill_keeps_the_partial_interval() {
+        let interval = Duration::from_secs(10);
+        let mut bucket = drained_bucket(5, interval);
+
+        bucket.last_refill = Instant::now() - Duration::from_secs(25);
+        bucket.refill();
+
+        let elapsed = bucket.last_refill.elapsed();
+
+        assert!(elapsed >= Duration::from_secs(5));
+        assert!(elapsed < Duration::from_secs(6));
+    }
+
+    #[test]
+    fn refill_stops_at_capacity() {
+        let interval = Duration::from_secs(1);
+        let mut bucket = drained_bucket(3, interval);
+
+        bucket.last_refill = Instant::now() - Duration::from_secs(60);
+        bucket.refill();
+
+        assert_eq!(bucket.tokens, 3);
+        assert!(bucket.last_refill.elapsed() < interval);
+    }
+
+    #[test]
+    fn acquiring_from_an_empty_bucket_borrows_nothing() {
+        let mut bucket = drained_bucket(1, Duration::from_secs(60));
+
+        bucket.acquire();
+
+        assert_eq!(bucket.tokens, 0);
+
+        bucket.last_refill = Instant::now() - Duration::from_secs(60);
+
+        assert_eq!(bucket.time_until_available(), Duration::from_secs(0));
+        assert_eq!(bucket.tokens, 1);
+    }
+
+    #[test]
+    fn huge_intervals_do_not_overflow() {
+        let interval = Duration::from_secs(u64::max_value());
+        let mut bucket = drained_bucket(1, interval);
+
+        assert!(bucket.time_until_available() > Duration::from_secs(0));
+    }
+}